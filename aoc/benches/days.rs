@@ -0,0 +1,59 @@
+//! Benchmarks the parse, part1 and part2 stages of every day.
+//!
+//! Each day runs on its real input (`dayNN/input.txt`) when there is one, and
+//! otherwise on its first fixture, in which case only the parts that fixture
+//! has answers for are measured.
+
+use aoc::inputs;
+use common::{normalize, Solution};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::{fs, hint::black_box, path::Path};
+
+fn bench_day<S: Solution>(c: &mut Criterion, day: u32) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let (path, parts) = inputs::day_input(&root, day).unwrap();
+    let text = normalize(&fs::read_to_string(path).unwrap()).into_owned();
+    // Each run of a part gets a freshly parsed input, as days may cache work
+    // in their input the first time a part needs it.
+    let parse = || S::parse(&text).unwrap();
+
+    let mut group = c.benchmark_group(format!("day{:02}", day));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&text))));
+    if parts.contains(&1) {
+        group.bench_function("part1", |b| {
+            b.iter_batched_ref(parse, |p| S::part1(black_box(p)), BatchSize::LargeInput)
+        });
+    }
+    if parts.contains(&2) {
+        group.bench_function("part2", |b| {
+            b.iter_batched_ref(parse, |p| S::part2(black_box(p)), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c, 1);
+    bench_day::<day02::Day02>(c, 2);
+    bench_day::<day03::Day03>(c, 3);
+    bench_day::<day04::Day04>(c, 4);
+    bench_day::<day05::Day05>(c, 5);
+    bench_day::<day06::Day06>(c, 6);
+    bench_day::<day07::Day07>(c, 7);
+    bench_day::<day08::Day08>(c, 8);
+    bench_day::<day09::Day09>(c, 9);
+    bench_day::<day10::Day10>(c, 10);
+    bench_day::<day11::Day11>(c, 11);
+    bench_day::<day12::Day12>(c, 12);
+    bench_day::<day13::Day13>(c, 13);
+    bench_day::<day14::Day14>(c, 14);
+    bench_day::<day15::Day15>(c, 15);
+    bench_day::<day16::Day16>(c, 16);
+    bench_day::<day17::Day17>(c, 17);
+    bench_day::<day18::Day18>(c, 18);
+    bench_day::<day19::Day19>(c, 19);
+    bench_day::<day20::Day20>(c, 20);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
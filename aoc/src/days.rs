@@ -0,0 +1,88 @@
+use common::{normalize, ParseError, Solution};
+use std::time::{Duration, Instant};
+
+/// A day's `Solution`, with answers rendered to strings so every day can be
+/// dispatched through the same table.
+pub struct Day {
+    pub day: u32,
+    pub solve: fn(&str, &[u32], Found) -> Result<Run, ParseError>,
+    /// The day's explanation of its answers, if it has one.
+    pub explain: fn(&str) -> Result<Option<Vec<String>>, ParseError>,
+}
+
+/// Called with each answer as soon as it's found.
+pub type Found<'a> = &'a mut dyn FnMut(&str);
+
+/// The answers of a run, along with the wall time each stage took.
+pub struct Run {
+    pub parse_time: Duration,
+    /// One answer per requested part, in the same order.
+    pub answers: Vec<(String, Duration)>,
+}
+
+/// Parses `input` once and runs each of `parts` on it, passing each answer to
+/// `found` as it comes. Line endings are normalized first, so days only need
+/// to handle `\n`.
+fn solve<S: Solution>(input: &str, parts: &[u32], found: Found) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(&normalize(input))?;
+    let parse_time = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&input).to_string(),
+                _ => S::part2(&input).to_string(),
+            };
+            let time = start.elapsed();
+            found(&answer);
+            (answer, time)
+        })
+        .collect();
+    Ok(Run {
+        parse_time,
+        answers,
+    })
+}
+
+fn explain<S: Solution>(input: &str) -> Result<Option<Vec<String>>, ParseError> {
+    Ok(S::explain(&S::parse(&normalize(input))?))
+}
+
+macro_rules! day {
+    ($day:literal, $solution:ty) => {
+        Day {
+            day: $day,
+            solve: solve::<$solution>,
+            explain: explain::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day01::Day01),
+    day!(2, day02::Day02),
+    day!(3, day03::Day03),
+    day!(4, day04::Day04),
+    day!(5, day05::Day05),
+    day!(6, day06::Day06),
+    day!(7, day07::Day07),
+    day!(8, day08::Day08),
+    day!(9, day09::Day09),
+    day!(10, day10::Day10),
+    day!(11, day11::Day11),
+    day!(12, day12::Day12),
+    day!(13, day13::Day13),
+    day!(14, day14::Day14),
+    day!(15, day15::Day15),
+    day!(16, day16::Day16),
+    day!(17, day17::Day17),
+    day!(18, day18::Day18),
+    day!(19, day19::Day19),
+    day!(20, day20::Day20),
+];
+
+pub fn get(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,72 @@
+//! Puzzle inputs: the real ones in `dayNN/input.txt`, which aren't checked in,
+//! and the fixtures in `fixtures/dayNN/`.
+//!
+//! Each `<name>.txt` fixture sits next to a `<name>.answers` file holding
+//! `part1: <answer>` and/or `part2: <answer>` lines. Parts without a line
+//! aren't known, e.g. when the example doesn't apply to that part.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub struct Fixture {
+    pub day: u32,
+    pub name: String,
+    pub input: PathBuf,
+    pub answers: Vec<(u32, String)>,
+}
+
+fn parse_answers(text: &str) -> Vec<(u32, String)> {
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| match l.split_once(": ") {
+            Some(("part1", answer)) => (1, answer.to_string()),
+            Some(("part2", answer)) => (2, answer.to_string()),
+            _ => panic!("invalid answers line: {:?}", l),
+        })
+        .collect()
+}
+
+/// All fixtures under `root`, sorted by day and name.
+pub fn load_fixtures(root: &Path) -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    let Ok(dirs) = fs::read_dir(root.join("fixtures")) else {
+        return fixtures;
+    };
+    for entry in dirs {
+        let dir = entry.unwrap().path();
+        let Some(day) = dir
+            .file_name()
+            .and_then(|n| n.to_str()?.strip_prefix("day")?.parse().ok())
+        else {
+            continue;
+        };
+        for entry in fs::read_dir(&dir).unwrap() {
+            let answers = entry.unwrap().path();
+            if answers.extension().is_none_or(|e| e != "answers") {
+                continue;
+            }
+            fixtures.push(Fixture {
+                day,
+                name: answers.file_stem().unwrap().to_string_lossy().into_owned(),
+                input: answers.with_extension("txt"),
+                answers: parse_answers(&fs::read_to_string(&answers).unwrap()),
+            });
+        }
+    }
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    fixtures
+}
+
+/// The input to measure a day on, along with the parts that apply to it: the
+/// real input when there is one, and otherwise the day's first fixture.
+pub fn day_input(root: &Path, day: u32) -> Option<(PathBuf, Vec<u32>)> {
+    let real = root.join(format!("day{:02}/input.txt", day));
+    if real.exists() {
+        return Some((real, vec![1, 2]));
+    }
+    let fixture = load_fixtures(root).into_iter().find(|f| f.day == day)?;
+    let parts = fixture.answers.iter().map(|(part, _)| *part).collect();
+    Some((fixture.input, parts))
+}
@@ -0,0 +1,4 @@
+pub mod days;
+pub mod inputs;
+#[cfg(test)]
+mod regression;
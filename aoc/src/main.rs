@@ -0,0 +1,259 @@
+mod timing;
+
+use aoc::days;
+use std::{
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process::ExitCode,
+    time::Duration,
+};
+
+const USAGE: &str = "Usage:
+  aoc run --day <N> [--part <1|2>] [--explain] [<input>...]
+  aoc time [--day <N>] [--budget <ms>] [--json]";
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    day: u32,
+    // Both parts are run if unset.
+    part: Option<u32>,
+    // Print the day's explanation instead of the answers.
+    explain: bool,
+    // Read from stdin if empty, as is `-`.
+    inputs: Vec<String>,
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value.ok_or(format!("Missing value for {}", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_run_args(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut explain = false;
+    let mut inputs = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number("--day", args.next())?),
+            "--part" => match parse_number("--part", args.next())? {
+                p @ (1 | 2) => part = Some(p),
+                p => return Err(format!("Invalid part: {}", p)),
+            },
+            "--explain" => explain = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag: {}", flag)),
+            _ => inputs.push(arg),
+        }
+    }
+    Ok(RunArgs {
+        day: day.ok_or("Missing --day")?,
+        part,
+        explain,
+        inputs,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct TimeArgs {
+    // All days are timed if unset.
+    day: Option<u32>,
+    budget: Option<Duration>,
+    json: bool,
+}
+
+fn parse_time_args(mut args: impl Iterator<Item = String>) -> Result<TimeArgs, String> {
+    let mut time_args = TimeArgs {
+        day: None,
+        budget: None,
+        json: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => time_args.day = Some(parse_number("--day", args.next())?),
+            "--budget" => {
+                let ms = parse_number("--budget", args.next())?;
+                time_args.budget = Some(Duration::from_millis(ms.into()));
+            }
+            "--json" => time_args.json = true,
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(time_args)
+}
+
+fn read_input(name: &str) -> Result<String, String> {
+    let mut input = String::new();
+    match name {
+        "-" => std::io::stdin().read_to_string(&mut input).map(|_| input),
+        _ => std::fs::read_to_string(name),
+    }
+    .map_err(|e| format!("Failed to read {}: {}", name, e))
+}
+
+fn explain_one(day: &days::Day, name: &str) -> Result<(), String> {
+    let input = read_input(name)?;
+    let lines = (day.explain)(&input).map_err(|e| format!("Failed to parse {}: {}", name, e))?;
+    for line in lines.ok_or(format!("Day {} has no explanation", day.day))? {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn run_one(day: &days::Day, name: &str, parts: &[u32]) -> Result<(), String> {
+    let input = read_input(name)?;
+    (day.solve)(&input, parts, &mut |answer| println!("{}", answer))
+        .map_err(|e| format!("Failed to parse {}: {}", name, e))?;
+    Ok(())
+}
+
+/// Runs the day on each input in turn, carrying on past inputs that fail.
+fn run(args: &RunArgs) -> Result<(), String> {
+    let day = days::get(args.day).ok_or(format!("No solution for day {}", args.day))?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    // A day panicking on one input shouldn't stop the others, nor bury them
+    // under a backtrace.
+    panic::set_hook(Box::new(|info| eprintln!("{}", info)));
+    let run_one = |name: &str| {
+        let res = panic::catch_unwind(AssertUnwindSafe(|| match args.explain {
+            true => explain_one(day, name),
+            false => run_one(day, name, &parts),
+        }));
+        res.unwrap_or_else(|_| Err(format!("Day {} panicked on {}", day.day, name)))
+    };
+    let inputs = match args.inputs.as_slice() {
+        [] => return run_one("-"),
+        [name] => return run_one(name),
+        inputs => inputs,
+    };
+
+    let mut failed = 0;
+    for name in inputs {
+        println!("{}:", name);
+        if let Err(e) = run_one(name) {
+            eprintln!("{}", e);
+            failed += 1;
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} of {} inputs failed", n, inputs.len())),
+    }
+}
+
+/// Times each day on its real input, or on its first fixture if it has none.
+/// Inputs are looked up relative to the current directory.
+fn time(args: &TimeArgs) -> Result<(), String> {
+    let mut timings = Vec::new();
+    for day in days::DAYS {
+        if args.day.is_some_and(|d| d != day.day) {
+            continue;
+        }
+        match timing::measure(Path::new("."), day)? {
+            Some(t) => timings.push(t),
+            None => eprintln!("Skipping day {}: no input", day.day),
+        }
+    }
+
+    match args.json {
+        true => print!("{}", timing::json(&timings, args.budget)),
+        false => print!("{}", timing::table(&timings, args.budget)),
+    }
+    let over = timings.iter().filter(|t| t.over(args.budget)).count();
+    match (over, args.budget) {
+        (0, _) | (_, None) => Ok(()),
+        (n, Some(budget)) => Err(format!("{} day(s) over the {:?} budget", n, budget)),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let res = match args.next().as_deref() {
+        Some("run") => parse_run_args(args).and_then(|args| run(&args)),
+        Some("time") => parse_time_args(args).and_then(|args| time(&args)),
+        _ => Err(USAGE.to_string()),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn run_args() {
+        assert_eq!(
+            parse_run_args(args("--day 16 --part 2 input.txt")),
+            Ok(RunArgs {
+                day: 16,
+                part: Some(2),
+                explain: false,
+                inputs: vec!["input.txt".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_run_args(args("a.txt --day 3 b.txt")),
+            Ok(RunArgs {
+                day: 3,
+                part: None,
+                explain: false,
+                inputs: vec!["a.txt".to_string(), "b.txt".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_run_args(args("--day 2 --explain")),
+            Ok(RunArgs {
+                day: 2,
+                part: None,
+                explain: true,
+                inputs: vec![],
+            })
+        );
+        assert!(parse_run_args(args("--day 3 --part 3 input.txt")).is_err());
+        assert!(parse_run_args(args("--part 1 input.txt")).is_err());
+    }
+
+    #[test]
+    fn time_args() {
+        assert_eq!(
+            parse_time_args(args("--budget 50 --json")),
+            Ok(TimeArgs {
+                day: None,
+                budget: Some(Duration::from_millis(50)),
+                json: true,
+            })
+        );
+        assert_eq!(
+            parse_time_args(args("--day 6")),
+            Ok(TimeArgs {
+                day: Some(6),
+                budget: None,
+                json: false,
+            })
+        );
+        assert!(parse_time_args(args("--budget")).is_err());
+        assert!(parse_time_args(args("input.txt")).is_err());
+    }
+
+    #[test]
+    fn all_days_registered() {
+        for day in 1..=20 {
+            assert_eq!(days::get(day).map(|d| d.day), Some(day));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Checks every day against the known answers of its fixtures.
+
+use crate::{
+    days,
+    inputs::{load_fixtures, Fixture},
+};
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+fn diff(expected: &str, actual: &str) -> String {
+    let mut out = String::new();
+    for line in expected.lines() {
+        out += &format!("-{}\n", line);
+    }
+    for line in actual.lines() {
+        out += &format!("+{}\n", line);
+    }
+    out
+}
+
+/// Runs a fixture, returning a description of each mismatch. With `crlf`, the
+/// input is given Windows line endings and a trailing newline first.
+fn check(fixture: &Fixture, crlf: bool) -> Vec<String> {
+    let mut label = format!("day{:02}/{}", fixture.day, fixture.name);
+    let Some(day) = days::get(fixture.day) else {
+        return vec![format!("{}: no solution registered", label)];
+    };
+    let mut input = fs::read_to_string(&fixture.input).unwrap();
+    if crlf {
+        input = input.trim_end().replace('\n', "\r\n") + "\r\n";
+        label += " (CRLF)";
+    }
+    let parts: Vec<u32> = fixture.answers.iter().map(|(part, _)| *part).collect();
+    let answers = match panic::catch_unwind(AssertUnwindSafe(|| {
+        (day.solve)(&input, &parts, &mut |_| ())
+    })) {
+        Ok(Ok(run)) => run.answers,
+        Ok(Err(e)) => return vec![format!("{}: {}", label, e)],
+        Err(_) => return vec![format!("{}: panicked", label)],
+    };
+    fixture
+        .answers
+        .iter()
+        .zip(answers)
+        .filter(|((_, expected), (actual, _))| expected != actual)
+        .map(|((part, expected), (actual, _))| {
+            format!("{} part {}:\n{}", label, part, diff(expected, &actual))
+        })
+        .collect()
+}
+
+#[test]
+fn fixtures_match_known_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let fixtures = load_fixtures(&root);
+    for day in days::DAYS {
+        assert!(
+            fixtures.iter().any(|f| f.day == day.day),
+            "no fixture for day {}",
+            day.day
+        );
+    }
+
+    let failures: Vec<String> = fixtures
+        .iter()
+        .flat_map(|f| [check(f, false), check(f, true)])
+        .flatten()
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "{} answer(s) changed:\n\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
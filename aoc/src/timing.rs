@@ -0,0 +1,134 @@
+use aoc::{days::Day, inputs};
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+/// Wall time of each stage of one day.
+pub struct Timing {
+    pub day: u32,
+    pub input: String,
+    pub parse: Duration,
+    /// Unset for parts that don't apply to the input.
+    pub parts: [Option<Duration>; 2],
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().flatten().sum::<Duration>()
+    }
+
+    pub fn over(&self, budget: Option<Duration>) -> bool {
+        budget.is_some_and(|budget| self.total() > budget)
+    }
+}
+
+/// Runs `day` on its input under `root`, or returns `None` if it has none.
+pub fn measure(root: &Path, day: &Day) -> Result<Option<Timing>, String> {
+    let Some((path, parts)) = inputs::day_input(root, day.day) else {
+        return Ok(None);
+    };
+    let input = path
+        .strip_prefix(root)
+        .unwrap_or(&path)
+        .display()
+        .to_string();
+    let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", input, e))?;
+    let run = (day.solve)(&text, &parts, &mut |_| ())
+        .map_err(|e| format!("Failed to parse {}: {}", input, e))?;
+
+    let mut timing = Timing {
+        day: day.day,
+        input,
+        parse: run.parse_time,
+        parts: [None, None],
+    };
+    for (part, (_, time)) in parts.iter().zip(run.answers) {
+        timing.parts[*part as usize - 1] = Some(time);
+    }
+    Ok(Some(timing))
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), |t| format!("{:.1?}", t))
+}
+
+pub fn table(timings: &[Timing], budget: Option<Duration>) -> String {
+    let width = timings.iter().map(|t| t.input.len()).max().unwrap_or(0);
+    let mut out = format!(
+        "{:>3}  {:<width$}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+        "day", "input", "parse", "part1", "part2", "total"
+    );
+    for t in timings {
+        let row = format!(
+            "{:>3}  {:<width$}  {:>10}  {:>10}  {:>10}  {:>10}",
+            t.day,
+            t.input,
+            format_time(Some(t.parse)),
+            format_time(t.parts[0]),
+            format_time(t.parts[1]),
+            format_time(Some(t.total())),
+        );
+        match t.over(budget) {
+            true => writeln!(out, "{}  over budget", row).unwrap(),
+            false => writeln!(out, "{}", row.trim_end()).unwrap(),
+        }
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn json_nanos(time: Option<Duration>) -> String {
+    time.map_or("null".to_string(), |t| t.as_nanos().to_string())
+}
+
+pub fn json(timings: &[Timing], budget: Option<Duration>) -> String {
+    let rows: Vec<String> = timings
+        .iter()
+        .map(|t| {
+            format!(
+                "  {{\"day\": {}, \"input\": {}, \"parse_ns\": {}, \"part1_ns\": {}, \
+                 \"part2_ns\": {}, \"total_ns\": {}, \"over_budget\": {}}}",
+                t.day,
+                json_string(&t.input),
+                json_nanos(Some(t.parse)),
+                json_nanos(t.parts[0]),
+                json_nanos(t.parts[1]),
+                json_nanos(Some(t.total())),
+                t.over(budget),
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports() {
+        let timings = [Timing {
+            day: 6,
+            input: "day06/input.txt".to_string(),
+            parse: Duration::from_micros(20),
+            parts: [Some(Duration::from_millis(2)), None],
+        }];
+        assert_eq!(timings[0].total(), Duration::from_micros(2020));
+        assert!(timings[0].over(Some(Duration::from_millis(1))));
+        assert!(!timings[0].over(None));
+
+        let table = table(&timings, Some(Duration::from_millis(1)));
+        assert!(table
+            .lines()
+            .nth(1)
+            .unwrap()
+            .ends_with("2.0ms  over budget"));
+        assert_eq!(
+            json(&timings, None),
+            "[\n  {\"day\": 6, \"input\": \"day06/input.txt\", \"parse_ns\": 20000, \
+             \"part1_ns\": 2000000, \"part2_ns\": null, \"total_ns\": 2020000, \
+             \"over_budget\": false}\n]\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// 1-based line and column (in characters) of a spot in the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Locates `text` in `input`, if it's a slice of it.
+    pub fn of(input: &str, text: &str) -> Option<Location> {
+        let start = input.as_ptr() as usize;
+        let offset = (text.as_ptr() as usize).checked_sub(start)?;
+        if offset + text.len() > input.len() {
+            return None;
+        }
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Some(Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        })
+    }
+}
+
+/// Error returned when a puzzle input can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub msg: String,
+    pub location: Option<Location>,
+    /// The offending part of the input.
+    pub text: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(msg: impl Into<String>) -> ParseError {
+        ParseError {
+            msg: msg.into(),
+            location: None,
+            text: None,
+        }
+    }
+
+    /// Error about `text`, located by where it sits in `input`. `text` must be
+    /// a slice of `input` for the location to be known, which is the case for
+    /// anything obtained through `lines`, `split`, `trim` and the like.
+    pub fn at(input: &str, text: &str, msg: impl Into<String>) -> ParseError {
+        ParseError {
+            msg: msg.into(),
+            location: Location::of(input, text),
+            text: Some(text.to_string()),
+        }
+    }
+
+    /// Moves the location of an error found in `part`, a slice of `input`, to
+    /// where it is in `input`.
+    pub fn within(mut self, input: &str, part: &str) -> ParseError {
+        if let (Some(loc), Some(start)) = (&mut self.location, Location::of(input, part)) {
+            if loc.line == 1 {
+                loc.column += start.column - 1;
+            }
+            loc.line += start.line - 1;
+        }
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(loc) = self.location {
+            write!(f, "line {}, column {}: ", loc.line, loc.column)?;
+        }
+        write!(f, "{}", self.msg)?;
+        match self.text.as_deref().and_then(|t| t.lines().next()) {
+            Some(text) if !text.is_empty() => write!(f, ": `{}`", text),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text`, a slice of `input`, into a number.
+pub fn parse_num<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::at(input, text, "invalid number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_slices() {
+        let input = "12 34\n56 x7\n";
+        let text = input.lines().nth(1).unwrap().split(' ').nth(1).unwrap();
+        let err = parse_num::<i32>(input, text).unwrap_err();
+        assert_eq!(err.location, Some(Location { line: 2, column: 4 }));
+        assert_eq!(err.to_string(), "line 2, column 4: invalid number: `x7`");
+
+        assert_eq!(Location::of(input, "x7"), None);
+        assert_eq!(ParseError::new("empty input").to_string(), "empty input");
+
+        let part = &input[6..];
+        let err = parse_num::<i32>(part, &part[3..5]).unwrap_err();
+        assert_eq!(err.location, Some(Location { line: 1, column: 4 }));
+        assert_eq!(
+            err.within(input, part).location,
+            Some(Location { line: 2, column: 4 })
+        );
+    }
+}
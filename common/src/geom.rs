@@ -0,0 +1,261 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// 2D vector, used both for positions and for offsets between them. `y` grows
+/// downwards, as rows do in the puzzle inputs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Vec2<T> {
+        Vec2 { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn add(self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, other: Vec2<T>) -> Vec2<T> {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, other: Vec2<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, other: Vec2<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn neg(self) -> Vec2<T> {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// Scaling by a scalar.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn mul(self, k: T) -> Vec2<T> {
+        Vec2::new(self.x * k, self.y * k)
+    }
+}
+
+/// Scaling by a scalar, rounding towards zero for integers.
+impl<T: Div<Output = T> + Copy> Div<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn div(self, k: T) -> Vec2<T> {
+        Vec2::new(self.x / k, self.y / k)
+    }
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Vec2<$t> {
+            pub fn manhattan(self, other: Vec2<$t>) -> $t {
+                (self.x - other.x).abs() + (self.y - other.y).abs()
+            }
+
+            pub fn chebyshev(self, other: Vec2<$t>) -> $t {
+                (self.x - other.x).abs().max((self.y - other.y).abs())
+            }
+
+            /// Rotates by 90 degrees clockwise, as seen on screen.
+            pub fn rotate_right(self) -> Vec2<$t> {
+                Vec2::new(-self.y, self.x)
+            }
+
+            /// Rotates by 90 degrees counter-clockwise, as seen on screen.
+            pub fn rotate_left(self) -> Vec2<$t> {
+                Vec2::new(self.y, -self.x)
+            }
+
+            /// Component-wise modulo that always lands in `0..size`, for
+            /// wrapping around a `size.x` by `size.y` area.
+            pub fn rem_euclid(self, size: Vec2<$t>) -> Vec2<$t> {
+                Vec2::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+            }
+        }
+    )*};
+}
+
+impl_signed!(i32, i64, isize);
+
+/// The four orthogonal directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// Parses the `^`, `>`, `v` and `<` arrows used across the puzzles.
+    pub fn from_arrow(c: char) -> Option<Dir4> {
+        match c {
+            '^' => Some(Dir4::Up),
+            '>' => Some(Dir4::Right),
+            'v' => Some(Dir4::Down),
+            '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self as usize]
+    }
+
+    pub fn turn_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn delta(self) -> Vec2<i32> {
+        match self {
+            Dir4::Up => Vec2::new(0, -1),
+            Dir4::Right => Vec2::new(1, 0),
+            Dir4::Down => Vec2::new(0, 1),
+            Dir4::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+/// The four orthogonal and four diagonal directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// Turns by 45 degrees clockwise.
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns by 45 degrees counter-clockwise.
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn delta(self) -> Vec2<i32> {
+        match self {
+            Dir8::Up => Vec2::new(0, -1),
+            Dir8::UpRight => Vec2::new(1, -1),
+            Dir8::Right => Vec2::new(1, 0),
+            Dir8::DownRight => Vec2::new(1, 1),
+            Dir8::Down => Vec2::new(0, 1),
+            Dir8::DownLeft => Vec2::new(-1, 1),
+            Dir8::Left => Vec2::new(-1, 0),
+            Dir8::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Dir8 {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2::new(3, -2);
+        let b = Vec2::new(-1, 5);
+        assert_eq!(a + b, Vec2::new(2, 3));
+        assert_eq!(a - b, Vec2::new(4, -7));
+        assert_eq!(-a, Vec2::new(-3, 2));
+        assert_eq!(a * 3, Vec2::new(9, -6));
+        assert_eq!(Vec2::new(7, 103) / 2, Vec2::new(3, 51));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec2::new(1i64, 2);
+        let b = Vec2::new(4, -4);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+    }
+
+    #[test]
+    fn rem_euclid_wraps_negatives() {
+        let size = Vec2::new(11, 7);
+        assert_eq!(Vec2::new(-1i32, 15).rem_euclid(size), Vec2::new(10, 1));
+    }
+
+    #[test]
+    fn rotations_match_turns() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.delta().rotate_right(), dir.turn_right().delta());
+            assert_eq!(dir.delta().rotate_left(), dir.turn_left().delta());
+            assert_eq!(-dir.delta(), dir.opposite().delta());
+            assert_eq!(Dir4::from_arrow(dir.arrow()), Some(dir));
+            assert_eq!(Dir8::from(dir).delta(), dir.delta());
+        }
+        for dir in Dir8::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(-dir.delta(), dir.opposite().delta());
+        }
+        assert_eq!(Dir8::UpRight.turn_right(), Dir8::Right);
+    }
+}
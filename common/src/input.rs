@@ -0,0 +1,23 @@
+use std::borrow::Cow;
+
+/// Converts CRLF line endings to LF and drops trailing newlines, so parsers
+/// only ever see `\n` between lines and nothing after the last one.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.trim_end_matches(['\r', '\n']);
+    match input.contains('\r') {
+        true => Cow::Owned(input.replace("\r\n", "\n")),
+        false => Cow::Borrowed(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_endings() {
+        assert_eq!(normalize("1 2\r\n3 4\r\n\r\n"), "1 2\n3 4");
+        assert_eq!(normalize("a\n\nb\n"), "a\n\nb");
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed("a\nb")));
+    }
+}
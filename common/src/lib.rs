@@ -0,0 +1,12 @@
+mod error;
+pub mod geom;
+mod input;
+mod params;
+mod rng;
+mod solution;
+
+pub use error::{parse_num, Location, ParseError};
+pub use input::normalize;
+pub use params::Params;
+pub use rng::Rng;
+pub use solution::Solution;
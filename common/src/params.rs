@@ -0,0 +1,106 @@
+use crate::{parse_num, ParseError};
+use std::str::FromStr;
+
+/// Puzzle parameters given as `# key=value` lines at the top of an input, for
+/// the days whose examples use a different size than the real puzzle.
+#[derive(Debug, Default)]
+pub struct Params<'a> {
+    input: &'a str,
+    values: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Params<'a> {
+    /// Splits the parameter lines off `input`, returning them along with the
+    /// rest of the input. Keys not in `known` are rejected.
+    pub fn parse(input: &'a str, known: &[&str]) -> Result<(Params<'a>, &'a str), ParseError> {
+        let mut params = Params {
+            input,
+            values: Vec::new(),
+        };
+        let mut rest = input;
+        while let Some(line) = rest.strip_prefix("# ") {
+            let (line, next) = line.split_once('\n').unwrap_or((line, ""));
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(ParseError::at(input, line, "expected `# <key>=<value>`"));
+            };
+            if !known.contains(&key) {
+                return Err(ParseError::at(
+                    input,
+                    key,
+                    format!("unknown parameter, expected one of {}", known.join(", ")),
+                ));
+            }
+            params.values.push((key, value));
+            rest = next;
+        }
+        Ok((params, rest))
+    }
+
+    /// The value of `key`, or `default` if the input doesn't set it.
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, ParseError> {
+        match self.values.iter().rev().find(|(k, _)| *k == key) {
+            Some((_, value)) => parse_num(self.input, value),
+            None => Ok(default),
+        }
+    }
+
+    /// Like `get`, but a value set by the input for which `valid` is false is
+    /// rejected with `msg`.
+    pub fn get_valid<T: FromStr>(
+        &self,
+        key: &str,
+        default: T,
+        valid: impl Fn(&T) -> bool,
+        msg: &str,
+    ) -> Result<T, ParseError> {
+        match self.values.iter().rev().find(|(k, _)| *k == key) {
+            Some((_, value)) => {
+                let n = parse_num(self.input, value)?;
+                match valid(&n) {
+                    true => Ok(n),
+                    false => Err(ParseError::at(self.input, value, msg)),
+                }
+            }
+            None => Ok(default),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_lines() {
+        let input = "# width=11\n# height=7\np=0,4 v=3,-3";
+        let (params, rest) = Params::parse(input, &["width", "height"]).unwrap();
+        assert_eq!(rest, "p=0,4 v=3,-3");
+        assert_eq!(params.get("width", 101), Ok(11));
+        assert_eq!(params.get("height", 103), Ok(7));
+
+        let (params, rest) = Params::parse("1,2", &["width"]).unwrap();
+        assert_eq!((params.get("width", 101), rest), (Ok(101), "1,2"));
+
+        let err = Params::parse("# depth=3\n", &["width"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: unknown parameter, expected one of width: `depth`"
+        );
+        let (params, _) = Params::parse("# width=x\n", &["width"]).unwrap();
+        assert!(params.get("width", 0).is_err());
+
+        let (params, _) = Params::parse("# width=0\n", &["width"]).unwrap();
+        let positive = |&n: &i32| n > 0;
+        assert_eq!(
+            params
+                .get_valid("width", 101, positive, "must be positive")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 9: must be positive: `0`"
+        );
+        assert_eq!(
+            params.get_valid("height", 103, positive, "must be positive"),
+            Ok(103)
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use crate::ParseError;
+use std::fmt::Display;
+
+/// A day's puzzle, split into a parsing stage and two solving stages so each
+/// can be run (and measured) on its own.
+pub trait Solution {
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+
+    /// Human-readable reasoning behind the answers, one line per item of the
+    /// input, for the days that can explain themselves.
+    fn explain(_input: &Self::Input) -> Option<Vec<String>> {
+        None
+    }
+}
@@ -0,0 +1,59 @@
+//! Computes day01's total distance and similarity score of a two-column file
+//! that may not fit in memory:
+//!
+//!     cargo run --release -p day01 --example stream -- [--separator <sep>] [--chunk <n>] [<file>]
+//!
+//! Reads stdin when no file is given. `--chunk` is the number of values per
+//! column kept in memory; `--separator` defaults to any run of whitespace.
+
+use day01::{stream_totals, Separator, StreamOptions};
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    process::ExitCode,
+};
+
+fn run() -> Result<(i64, i64), String> {
+    let mut opts = StreamOptions::default();
+    let mut input = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--separator" => {
+                opts.separator = Separator::new(&args.next().ok_or("Missing separator")?);
+            }
+            "--chunk" => {
+                let n = args.next().ok_or("Missing chunk length")?;
+                opts.chunk_len = n
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or(format!("Invalid chunk length: {}", n))?;
+            }
+            _ => input = Some(arg),
+        }
+    }
+
+    let res = match &input {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            stream_totals(BufReader::new(file), &opts)
+        }
+        None => stream_totals(io::stdin().lock(), &opts),
+    };
+    res.map_err(|e| format!("{}: {}", input.as_deref().unwrap_or("-"), e))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok((distance, score)) => {
+            println!("{}", distance);
+            println!("{}", score);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
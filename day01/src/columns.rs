@@ -0,0 +1,178 @@
+//! Any number of columns, compared pairwise.
+
+use crate::{similarity_score, Separator};
+use common::{parse_num, ParseError};
+
+/// How far apart two values paired up by sorted position are.
+pub trait Metric {
+    fn distance(&self, a: i64, b: i64) -> f64;
+}
+
+impl<F: Fn(i64, i64) -> f64> Metric for F {
+    fn distance(&self, a: i64, b: i64) -> f64 {
+        self(a, b)
+    }
+}
+
+/// `|a - b|`, as in the puzzle.
+pub struct Absolute;
+
+/// `(a - b)²`.
+pub struct Squared;
+
+/// `|a - b|` as a fraction of the larger magnitude, so in `0..=2`.
+pub struct Relative;
+
+/// `|a - b|`, but at most the given cap, so outliers don't dominate.
+pub struct Capped(pub i64);
+
+impl Metric for Absolute {
+    fn distance(&self, a: i64, b: i64) -> f64 {
+        a.abs_diff(b) as f64
+    }
+}
+
+impl Metric for Squared {
+    fn distance(&self, a: i64, b: i64) -> f64 {
+        (a.abs_diff(b) as f64).powi(2)
+    }
+}
+
+impl Metric for Relative {
+    fn distance(&self, a: i64, b: i64) -> f64 {
+        match a.unsigned_abs().max(b.unsigned_abs()) {
+            0 => 0.0,
+            max => a.abs_diff(b) as f64 / max as f64,
+        }
+    }
+}
+
+impl Metric for Capped {
+    fn distance(&self, a: i64, b: i64) -> f64 {
+        a.abs_diff(b).min(self.0.unsigned_abs()) as f64
+    }
+}
+
+/// Columns of the same length, each kept sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns {
+    cols: Vec<Vec<i64>>,
+}
+
+impl Columns {
+    /// The columns must all have the same length, which `parse` checks.
+    fn new(mut cols: Vec<Vec<i64>>) -> Columns {
+        debug_assert!(cols.windows(2).all(|w| w[0].len() == w[1].len()));
+        for col in &mut cols {
+            col.sort_unstable();
+        }
+        Columns { cols }
+    }
+
+    /// Parses `lines`, a slice of `input`, into as many columns as its first
+    /// line has.
+    pub fn parse(input: &str, lines: &str, separator: &Separator) -> Result<Columns, ParseError> {
+        let mut cols: Vec<Vec<i64>> = Vec::new();
+        for (i, line) in lines.lines().enumerate() {
+            let values = separator
+                .columns(line)
+                .map(|v| parse_num(input, v))
+                .collect::<Result<Vec<i64>, _>>()?;
+            if i == 0 {
+                cols = vec![Vec::new(); values.len()];
+            } else if values.len() != cols.len() {
+                let msg = format!("expected {} columns", cols.len());
+                return Err(ParseError::at(input, line, msg));
+            }
+            for (col, value) in cols.iter_mut().zip(values) {
+                col.push(value);
+            }
+        }
+        Ok(Columns::new(cols))
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cols.is_empty()
+    }
+
+    /// The values of column `i` in ascending order.
+    pub fn sorted(&self, i: usize) -> impl Iterator<Item = i64> + '_ {
+        self.cols[i].iter().copied()
+    }
+
+    fn matrix<T>(&self, f: impl Fn(usize, usize) -> T) -> Vec<Vec<T>> {
+        (0..self.len())
+            .map(|i| (0..self.len()).map(|j| f(i, j)).collect())
+            .collect()
+    }
+
+    /// Total distance between every pair of columns, with values paired up in
+    /// sorted order: entry `[i][j]` compares column `i` with column `j`.
+    pub fn distance_matrix(&self, metric: &impl Metric) -> Vec<Vec<f64>> {
+        self.matrix(|i, j| {
+            self.sorted(i)
+                .zip(self.sorted(j))
+                .map(|(a, b)| metric.distance(a, b))
+                .sum()
+        })
+    }
+
+    /// Similarity score between every pair of columns: entry `[i][j]` weighs
+    /// each value of column `i` by how often it appears in column `j`.
+    pub fn similarity_matrix(&self) -> Vec<Vec<i64>> {
+        self.matrix(|i, j| similarity_score(self.sorted(i), self.sorted(j)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Columns {
+        let input = "3 4 1\n4 3 1\n2 5 3\n1 3 3\n3 9 3\n3 3 4";
+        Columns::parse(input, input, &Separator::Whitespace).unwrap()
+    }
+
+    #[test]
+    fn matrices() {
+        let columns = columns();
+        assert_eq!(columns.len(), 3);
+        let dist = columns.distance_matrix(&Absolute);
+        assert_eq!(dist[0][1], 11.0);
+        assert_eq!(dist[1][0], 11.0);
+        assert_eq!(dist[0][2], 1.0);
+        assert_eq!(dist[2][2], 0.0);
+        let sim = columns.similarity_matrix();
+        assert_eq!(sim[0][1], 31);
+        assert_eq!(sim[0][2], 2 + 3 * 3 * 3 + 4);
+        assert_eq!(sim[2][2], 2 * 2 + 3 * 3 * 3 + 4);
+    }
+
+    #[test]
+    fn metrics() {
+        let columns = Columns::new(vec![vec![0, 10, 100], vec![0, 5, 300]]);
+        assert_eq!(columns.distance_matrix(&Squared)[0][1], 25.0 + 40000.0);
+        assert_eq!(
+            columns.distance_matrix(&Relative)[0][1],
+            0.5 + 200.0 / 300.0
+        );
+        assert_eq!(columns.distance_matrix(&Capped(50))[0][1], 5.0 + 50.0);
+        let custom = |a: i64, b: i64| (a != b) as i64 as f64;
+        assert_eq!(columns.distance_matrix(&custom)[1][0], 2.0);
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let input = "1 2 3\n4 5";
+        let err = Columns::parse(input, input, &Separator::Whitespace).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected 3 columns: `4 5`"
+        );
+    }
+}
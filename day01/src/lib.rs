@@ -0,0 +1,99 @@
+mod columns;
+mod stream;
+
+use common::{Params, ParseError, Solution};
+
+pub use columns::{Absolute, Capped, Columns, Metric, Relative, Squared};
+pub use stream::{stream_totals, StreamError, StreamOptions};
+
+/// How the columns of a line are separated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Separator {
+    /// Any run of whitespace, as in the puzzle input.
+    Whitespace,
+    /// A fixed string, e.g. `,`. Whitespace around the columns is ignored.
+    Text(String),
+}
+
+impl Separator {
+    /// Parses a separator given on the command line or in a `# separator=`
+    /// input header, where `\t` stands for a tab.
+    pub fn new(sep: &str) -> Separator {
+        match sep {
+            "" => Separator::Whitespace,
+            sep => Separator::Text(sep.replace("\\t", "\t")),
+        }
+    }
+
+    fn columns<'s, 'a: 's>(&'s self, line: &'a str) -> Box<dyn Iterator<Item = &'a str> + 's> {
+        match self {
+            Separator::Whitespace => Box::new(line.split_whitespace()),
+            Separator::Text(sep) => Box::new(line.split(sep.as_str()).map(str::trim)),
+        }
+    }
+
+    fn split<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        let mut cols = self.columns(line);
+        match (cols.next(), cols.next(), cols.next()) {
+            (Some(a), Some(b), None) => Some((a, b)),
+            _ => None,
+        }
+    }
+}
+
+/// Sum of the distances between the values of the two columns, paired up in
+/// sorted order. Both columns must be sorted.
+pub fn total_distance(left: impl Iterator<Item = i64>, right: impl Iterator<Item = i64>) -> i64 {
+    left.zip(right).map(|(a, b)| (a - b).abs()).sum()
+}
+
+/// Sum of each left value times the number of times it appears on the right.
+/// Both columns must be sorted, so they can be walked through in step.
+pub fn similarity_score(left: impl Iterator<Item = i64>, right: impl Iterator<Item = i64>) -> i64 {
+    let mut right = right.peekable();
+    let mut counted = None;
+    let mut count = 0;
+    let mut score = 0;
+    for a in left {
+        if counted != Some(a) {
+            while right.next_if(|&b| b < a).is_some() {}
+            count = 0;
+            while right.next_if(|&b| b == a).is_some() {
+                count += 1;
+            }
+            counted = Some(a);
+        }
+        score += a * count;
+    }
+    score
+}
+
+fn parse(contents: &str) -> Result<Columns, ParseError> {
+    let (params, lines) = Params::parse(contents, &["separator"])?;
+    let separator = Separator::new(&params.get("separator", String::new())?);
+    let columns = Columns::parse(contents, lines, &separator)?;
+    if columns.len() != 2 {
+        return Err(ParseError::at(contents, lines, "expected two columns"));
+    }
+    Ok(columns)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Columns;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(columns: &Self::Input) -> i64 {
+        total_distance(columns.sorted(0), columns.sorted(1))
+    }
+
+    fn part2(columns: &Self::Input) -> i64 {
+        similarity_score(columns.sorted(0), columns.sorted(1))
+    }
+}
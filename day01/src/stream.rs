@@ -0,0 +1,275 @@
+//! Streaming mode for inputs larger than memory. Each column is sorted in
+//! chunks, which are spilled to temporary files and merged back when computing
+//! the totals.
+
+use crate::{similarity_score, total_distance, Separator};
+use common::{Location, ParseError};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    pub separator: Separator,
+    /// Values per column kept in memory before a sorted chunk is spilled.
+    pub chunk_len: usize,
+    /// Where the spilled chunks go.
+    pub tmp_dir: PathBuf,
+}
+
+impl Default for StreamOptions {
+    fn default() -> StreamOptions {
+        StreamOptions {
+            separator: Separator::Whitespace,
+            chunk_len: 1 << 22,
+            tmp_dir: env::temp_dir(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> StreamError {
+        StreamError::Io(e)
+    }
+}
+
+/// Error about `text`, a slice of line `line_no` of the input.
+fn parse_error(line_no: usize, line: &str, text: &str, msg: &str) -> StreamError {
+    let mut e = ParseError::at(line, text, msg);
+    e.location = e.location.map(|loc| Location {
+        line: line_no,
+        ..loc
+    });
+    StreamError::Parse(e)
+}
+
+static RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// One column, as sorted runs on disk plus a sorted tail still in memory. The
+/// files are removed on drop.
+#[derive(Default)]
+struct SortedRuns {
+    files: Vec<PathBuf>,
+    tail: Vec<i64>,
+}
+
+impl Drop for SortedRuns {
+    fn drop(&mut self) {
+        for file in &self.files {
+            let _ = fs::remove_file(file);
+        }
+    }
+}
+
+impl SortedRuns {
+    fn push(&mut self, value: i64, opts: &StreamOptions) -> io::Result<()> {
+        self.tail.push(value);
+        if self.tail.len() >= opts.chunk_len {
+            self.spill(opts)?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self, opts: &StreamOptions) -> io::Result<()> {
+        self.tail.sort_unstable();
+        let id = RUN_ID.fetch_add(1, Ordering::Relaxed);
+        let path = opts
+            .tmp_dir
+            .join(format!("day01-{}-{}.run", process::id(), id));
+        // Registered before writing so a failed write is cleaned up too.
+        self.files.push(path.clone());
+        let mut out = BufWriter::new(File::create(path)?);
+        for value in self.tail.drain(..) {
+            out.write_all(&value.to_le_bytes())?;
+        }
+        out.flush()
+    }
+
+    fn merge(&self) -> io::Result<Merge<'_>> {
+        let mut sources = vec![Source::Memory(self.tail.iter())];
+        for file in &self.files {
+            sources.push(Source::Disk(BufReader::new(File::open(file)?)));
+        }
+        let mut heap = BinaryHeap::new();
+        for (i, source) in sources.iter_mut().enumerate() {
+            if let Some(value) = source.next_value()? {
+                heap.push(Reverse((value, i)));
+            }
+        }
+        Ok(Merge { sources, heap })
+    }
+}
+
+enum Source<'a> {
+    Memory(std::slice::Iter<'a, i64>),
+    Disk(BufReader<File>),
+}
+
+impl Source<'_> {
+    fn next_value(&mut self) -> io::Result<Option<i64>> {
+        match self {
+            Source::Memory(values) => Ok(values.next().copied()),
+            Source::Disk(reader) => {
+                let mut buf = [0; 8];
+                match reader.read_exact(&mut buf) {
+                    Ok(()) => Ok(Some(i64::from_le_bytes(buf))),
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+/// K-way merge of the sorted runs of a column.
+struct Merge<'a> {
+    sources: Vec<Source<'a>>,
+    heap: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl Iterator for Merge<'_> {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<io::Result<i64>> {
+        let Reverse((value, i)) = self.heap.pop()?;
+        match self.sources[i].next_value() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, i))),
+            Ok(None) => (),
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(value))
+    }
+}
+
+/// Runs `f` on both columns in sorted order.
+fn merged<T>(
+    left: &SortedRuns,
+    right: &SortedRuns,
+    f: impl FnOnce(&mut dyn Iterator<Item = i64>, &mut dyn Iterator<Item = i64>) -> T,
+) -> io::Result<T> {
+    let (mut left_err, mut right_err) = (None, None);
+    let res = {
+        let mut l = left
+            .merge()?
+            .map_while(|v| v.map_err(|e| left_err = Some(e)).ok());
+        let mut r = right
+            .merge()?
+            .map_while(|v| v.map_err(|e| right_err = Some(e)).ok());
+        f(&mut l, &mut r)
+    };
+    match left_err.or(right_err) {
+        Some(e) => Err(e),
+        None => Ok(res),
+    }
+}
+
+/// Reads a two-column input and returns its total distance and similarity
+/// score, keeping at most `opts.chunk_len` values per column in memory.
+pub fn stream_totals(
+    mut reader: impl BufRead,
+    opts: &StreamOptions,
+) -> Result<(i64, i64), StreamError> {
+    let mut left = SortedRuns::default();
+    let mut right = SortedRuns::default();
+    let mut buf = String::new();
+    let mut line_no = 0;
+    loop {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+        line_no += 1;
+        let line = buf.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            continue;
+        }
+        let Some((a, b)) = opts.separator.split(line) else {
+            return Err(parse_error(line_no, line, line, "expected two columns"));
+        };
+        for (col, text) in [(&mut left, a), (&mut right, b)] {
+            let value = text
+                .parse()
+                .map_err(|_| parse_error(line_no, line, text, "invalid number"))?;
+            col.push(value, opts)?;
+        }
+    }
+    left.tail.sort_unstable();
+    right.tail.sort_unstable();
+
+    let distance = merged(&left, &right, |l, r| total_distance(l, r))?;
+    let score = merged(&left, &right, |l, r| similarity_score(l, r))?;
+    Ok((distance, score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day01;
+    use common::{Rng, Solution};
+
+    fn totals(input: &str, opts: &StreamOptions) -> (i64, i64) {
+        stream_totals(input.as_bytes(), opts).unwrap()
+    }
+
+    #[test]
+    fn spilled_runs_match_in_memory() {
+        // Deterministic pseudo-random columns with plenty of duplicates.
+        let mut rng = Rng::new(42);
+        let input: String = (0..1000)
+            .map(|_| format!("{}   {}\n", rng.below(50), rng.below(50)))
+            .collect();
+        let parsed = Day01::parse(input.trim_end()).unwrap();
+        let expected = (Day01::part1(&parsed), Day01::part2(&parsed));
+
+        let opts = StreamOptions {
+            chunk_len: 7,
+            ..StreamOptions::default()
+        };
+        assert_eq!(totals(&input, &opts), expected);
+        assert_eq!(totals(&input, &StreamOptions::default()), expected);
+    }
+
+    #[test]
+    fn separators_and_errors() {
+        let opts = StreamOptions {
+            separator: Separator::new(","),
+            chunk_len: 2,
+            ..StreamOptions::default()
+        };
+        let input = "3,4\r\n4, 3\r\n2,5\r\n1,3\r\n3,9\r\n3,3\r\n";
+        assert_eq!(totals(input, &opts), (11, 31));
+
+        let err = stream_totals("3,4\n4,x\n".as_bytes(), &opts).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: invalid number: `x`");
+        let err = stream_totals("3,4,5\n".as_bytes(), &opts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected two columns: `3,4,5`"
+        );
+    }
+}
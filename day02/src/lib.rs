@@ -0,0 +1,530 @@
+use common::{parse_num, ParseError, Solution};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .map(|line| line.split(" ").map(|n| parse_num(input, n)).collect())
+        .collect()
+}
+
+fn is_safe(report: &[i32]) -> bool {
+    // Widened, as levels far apart would overflow an `i32` difference.
+    let diffs: Vec<i64> = report
+        .windows(2)
+        .map(|w| w[1] as i64 - w[0] as i64)
+        .collect();
+    diffs.iter().all(|&d| (1..=3).contains(&d)) || diffs.iter().all(|&d| (-3..=-1).contains(&d))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+impl Direction {
+    fn allows(self, a: i32, b: i32) -> bool {
+        match self {
+            Direction::Increasing => (1..=3).contains(&(b as i64 - a as i64)),
+            Direction::Decreasing => (1..=3).contains(&(a as i64 - b as i64)),
+        }
+    }
+
+    /// Index of the first pair of adjacent levels that breaks the rules.
+    fn first_violation(self, report: &[i32]) -> Option<usize> {
+        report.windows(2).position(|w| !self.allows(w[0], w[1]))
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Increasing => write!(f, "increasing"),
+            Direction::Decreasing => write!(f, "decreasing"),
+        }
+    }
+}
+
+/// Why a report is safe or not, when up to one level may be removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Safe(Direction),
+    /// Safe once the level at `index` is removed.
+    SafeWithout {
+        index: usize,
+        level: i32,
+        direction: Direction,
+    },
+    /// Unsafe even with one level removed. `index` is the first level of the
+    /// first pair that breaks the rules, in the direction the report keeps to
+    /// the longest.
+    Unsafe {
+        index: usize,
+        pair: (i32, i32),
+    },
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe { .. })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Safe(direction) => write!(f, "safe, {}", direction),
+            Verdict::SafeWithout {
+                index,
+                level,
+                direction,
+            } => write!(
+                f,
+                "safe after removing level {} at index {}, {}",
+                level, index, direction
+            ),
+            Verdict::Unsafe { index, pair } => write!(
+                f,
+                "unsafe, {} -> {} at index {} breaks the rules",
+                pair.0, pair.1, index
+            ),
+        }
+    }
+}
+
+/// Classifies a report, trying the removal of one level if it isn't safe as
+/// is. Once a direction is fixed, a bad pair can only be fixed by removing one
+/// of its two levels, so only those are tried.
+pub fn verdict(report: &[i32]) -> Verdict {
+    let directions = [Direction::Increasing, Direction::Decreasing];
+    let violations = directions.map(|d| d.first_violation(report));
+    for (direction, violation) in directions.into_iter().zip(violations) {
+        if violation.is_none() {
+            return Verdict::Safe(direction);
+        }
+    }
+    for (direction, violation) in directions.into_iter().zip(violations) {
+        let i = violation.unwrap();
+        for index in [i, i + 1] {
+            let mut rest = report.to_vec();
+            let level = rest.remove(index);
+            if direction.first_violation(&rest).is_none() {
+                return Verdict::SafeWithout {
+                    index,
+                    level,
+                    direction,
+                };
+            }
+        }
+    }
+    let index = violations.into_iter().flatten().max().unwrap();
+    Verdict::Unsafe {
+        index,
+        pair: (report[index], report[index + 1]),
+    }
+}
+
+/// What makes a report safe: every step between adjacent levels must go the
+/// same way, by `min_step..=max_step`, except for up to `plateaus` steps
+/// where the level stays the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    min_step: i32,
+    max_step: i32,
+    increasing: bool,
+    decreasing: bool,
+    plateaus: usize,
+}
+
+/// Why `Rules::new` rejects a step range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidRules {
+    /// Steps are sizes, the direction being given separately.
+    NegativeStep,
+    EmptySteps,
+}
+
+impl Display for InvalidRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidRules::NegativeStep => write!(f, "steps can't be negative"),
+            InvalidRules::EmptySteps => write!(f, "min_step is larger than max_step"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidRules {}
+
+impl Default for Rules {
+    /// The rules of the puzzle.
+    fn default() -> Rules {
+        Rules {
+            min_step: 1,
+            max_step: 3,
+            increasing: true,
+            decreasing: true,
+            plateaus: 0,
+        }
+    }
+}
+
+impl Rules {
+    pub fn new(
+        min_step: i32,
+        max_step: i32,
+        increasing: bool,
+        decreasing: bool,
+        plateaus: usize,
+    ) -> Result<Rules, InvalidRules> {
+        if min_step < 0 {
+            return Err(InvalidRules::NegativeStep);
+        }
+        if min_step > max_step {
+            return Err(InvalidRules::EmptySteps);
+        }
+        Ok(Rules {
+            min_step,
+            max_step,
+            increasing,
+            decreasing,
+            plateaus,
+        })
+    }
+
+    fn directions(&self) -> impl Iterator<Item = Direction> {
+        [
+            (self.increasing, Direction::Increasing),
+            (self.decreasing, Direction::Decreasing),
+        ]
+        .into_iter()
+        .filter_map(|(allowed, direction)| allowed.then_some(direction))
+    }
+
+    /// How many plateaus stepping from `a` to `b` uses up, or `None` if the
+    /// step isn't allowed at all.
+    fn step(&self, direction: Direction, a: i32, b: i32) -> Option<usize> {
+        let step = match direction {
+            Direction::Increasing => b.checked_sub(a)?,
+            Direction::Decreasing => a.checked_sub(b)?,
+        };
+        if (self.min_step..=self.max_step).contains(&step) {
+            Some(0)
+        } else if step == 0 && self.plateaus > 0 {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Length of the longest safe subsequence of `report` going in
+    /// `direction`. Levels are scanned in order, keeping for each value and
+    /// number of plateaus `p` the longest subsequence ending on that value
+    /// with `p` plateaus. A level extends the best subsequences ending on the
+    /// values a step away from it, so this takes O(n · s · plateaus) for `s`
+    /// the number of step sizes or of distinct values, whichever is smaller:
+    /// linear in the length of the report for a narrow step range, quadratic
+    /// at worst for a wide one.
+    fn longest_safe(&self, report: &[i32], direction: Direction) -> usize {
+        let max_plateaus = self.plateaus.min(report.len());
+        let mut best: HashMap<i32, Vec<usize>> = HashMap::new();
+        let mut longest = 0;
+        for &level in report {
+            // The longest subsequences this level can end, by plateaus.
+            let mut ending = vec![0; max_plateaus + 1];
+            ending[0] = 1;
+            let mut extend = |prev: &[usize], cost: usize| {
+                for p in 0..=max_plateaus - cost {
+                    if prev[p] > 0 {
+                        ending[p + cost] = ending[p + cost].max(prev[p] + 1);
+                    }
+                }
+            };
+            let steps = (self.max_step - self.min_step) as usize + 1;
+            if steps <= best.len() {
+                for step in self.min_step..=self.max_step {
+                    let prev = match direction {
+                        Direction::Increasing => level.checked_sub(step),
+                        Direction::Decreasing => level.checked_add(step),
+                    };
+                    if let Some(prev) = prev.and_then(|prev| best.get(&prev)) {
+                        extend(prev, 0);
+                    }
+                }
+            } else {
+                for (&prev, lengths) in &best {
+                    if self.step(direction, prev, level) == Some(0) {
+                        extend(lengths, 0);
+                    }
+                }
+            }
+            if self.plateaus > 0 && self.step(direction, level, level) == Some(1) {
+                if let Some(prev) = best.get(&level) {
+                    extend(prev, 1);
+                }
+            }
+            let lengths = best
+                .entry(level)
+                .or_insert_with(|| vec![0; max_plateaus + 1]);
+            for (best, ending) in lengths.iter_mut().zip(ending) {
+                *best = (*best).max(ending);
+                longest = longest.max(*best);
+            }
+        }
+        longest
+    }
+
+    /// The fewest levels that need to be removed from `report` to make it safe.
+    pub fn min_removals(&self, report: &[i32]) -> usize {
+        let longest = match report.len() {
+            0 | 1 => report.len(),
+            _ => self
+                .directions()
+                .map(|direction| self.longest_safe(report, direction))
+                .max()
+                .unwrap_or(1),
+        };
+        report.len() - longest
+    }
+
+    /// Whether `report` is safe once at most `budget` levels are removed.
+    pub fn is_safe(&self, report: &[i32], budget: usize) -> bool {
+        self.min_removals(report) <= budget
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_reports(input)
+    }
+
+    fn part1(reports: &Self::Input) -> usize {
+        reports.iter().filter(|&r| is_safe(r)).count()
+    }
+
+    fn part2(reports: &Self::Input) -> usize {
+        reports.iter().filter(|&r| verdict(r).is_safe()).count()
+    }
+
+    fn explain(reports: &Self::Input) -> Option<Vec<String>> {
+        let lines = reports.iter().map(|r| {
+            let levels: Vec<String> = r.iter().map(i32::to_string).collect();
+            format!("{}: {}", levels.join(" "), verdict(r))
+        });
+        Some(lines.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Rng;
+
+    fn read_reports(filename: &str) -> Vec<Vec<i32>> {
+        parse_reports(&std::fs::read_to_string(filename).unwrap()).unwrap()
+    }
+
+    fn is_safe2_brute_force(reports: &[i32]) -> bool {
+        if is_safe(reports) {
+            println!("{:?} safe as is", reports);
+            return true;
+        }
+        for i in 0..reports.len() {
+            // Remove element i.
+            let mut reports2 = reports.to_vec();
+            reports2.remove(i);
+            if is_safe(&reports2) {
+                println!("{:?} safe after removing {}", reports, reports[i]);
+                return true;
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_verdict() {
+        for report in read_reports("../fixtures/day02/example.txt") {
+            let verdict = verdict(&report);
+            assert_eq!(verdict.is_safe(), is_safe2_brute_force(&report));
+            if let Verdict::SafeWithout { index, level, .. } = verdict {
+                let mut rest = report.clone();
+                assert_eq!(rest.remove(index), level);
+                assert!(is_safe(&rest));
+            }
+        }
+    }
+
+    #[test]
+    fn test_verdict_safe_as_is_equals_is_safe() {
+        for report in read_reports("../fixtures/day02/example.txt") {
+            assert_eq!(
+                matches!(verdict(&report), Verdict::Safe(_)),
+                is_safe(&report)
+            );
+        }
+    }
+
+    /// Whether `report` follows `rules` as is, checked step by step.
+    fn follows(rules: &Rules, report: &[i32]) -> bool {
+        let in_direction = |direction: Direction| {
+            let mut plateaus = 0;
+            report.windows(2).all(|w| {
+                let step = match direction {
+                    Direction::Increasing => w[1] - w[0],
+                    Direction::Decreasing => w[0] - w[1],
+                };
+                if step == 0 && !(rules.min_step..=rules.max_step).contains(&0) {
+                    plateaus += 1;
+                    return plateaus <= rules.plateaus;
+                }
+                (rules.min_step..=rules.max_step).contains(&step)
+            })
+        };
+        report.len() < 2 || rules.directions().any(in_direction)
+    }
+
+    /// Tries every way of removing up to `budget` levels.
+    fn is_safe_brute_force(rules: &Rules, report: &[i32], budget: usize) -> bool {
+        (0u32..1 << report.len())
+            .filter(|removed| removed.count_ones() as usize <= budget)
+            .any(|removed| {
+                let kept: Vec<i32> = (0..report.len())
+                    .filter(|i| removed & (1 << i) == 0)
+                    .map(|i| report[i])
+                    .collect();
+                follows(rules, &kept)
+            })
+    }
+
+    #[test]
+    fn verdict_matches_removing_each_level() {
+        let mut rng = Rng::new(13);
+        for _ in 0..5000 {
+            // Random walks, so that many reports are safe or nearly so.
+            let mut level = rng.below(20) as i32;
+            let report: Vec<i32> = (0..rng.below(9))
+                .map(|_| {
+                    level += rng.below(9) as i32 - 4;
+                    level
+                })
+                .collect();
+            let without = |i: usize| {
+                let mut rest = report.clone();
+                rest.remove(i);
+                rest
+            };
+            let expected = is_safe(&report) || (0..report.len()).any(|i| is_safe(&without(i)));
+            let verdict = verdict(&report);
+            assert_eq!(verdict.is_safe(), expected, "{:?}: {}", report, verdict);
+            match verdict {
+                Verdict::Safe(direction) => {
+                    assert_eq!(direction.first_violation(&report), None)
+                }
+                Verdict::SafeWithout {
+                    index,
+                    level,
+                    direction,
+                } => {
+                    assert!(!is_safe(&report));
+                    assert_eq!(report[index], level);
+                    assert_eq!(direction.first_violation(&without(index)), None);
+                }
+                Verdict::Unsafe { index, pair } => {
+                    assert_eq!(pair, (report[index], report[index + 1]))
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn min_removals_matches_brute_force() {
+        let mut rng = Rng::new(7);
+        for _ in 0..2000 {
+            let min_step = rng.below(3) as i32;
+            let rules = Rules::new(
+                min_step,
+                min_step + rng.below(4) as i32,
+                rng.below(4) != 0,
+                rng.below(4) != 0,
+                rng.below(3) as usize,
+            )
+            .unwrap();
+            let report: Vec<i32> = (0..rng.below(9)).map(|_| rng.below(10) as i32).collect();
+            let removals = rules.min_removals(&report);
+            for budget in 0..=report.len() {
+                assert_eq!(
+                    rules.is_safe(&report, budget),
+                    is_safe_brute_force(&rules, &report, budget),
+                    "{:?} {:?} budget {}",
+                    rules,
+                    report,
+                    budget
+                );
+            }
+            assert!(removals == 0 || !is_safe_brute_force(&rules, &report, removals - 1));
+        }
+    }
+
+    #[test]
+    fn default_rules_match_puzzle() {
+        let rules = Rules::default();
+        for report in read_reports("../fixtures/day02/example.txt") {
+            assert_eq!(rules.is_safe(&report, 0), is_safe(&report));
+            assert_eq!(rules.is_safe(&report, 1), verdict(&report).is_safe());
+        }
+        let plateaus = Rules {
+            plateaus: 1,
+            ..Rules::default()
+        };
+        assert_eq!(plateaus.min_removals(&[8, 6, 4, 4, 1]), 0);
+        assert_eq!(plateaus.min_removals(&[8, 6, 6, 4, 4, 1]), 1);
+        assert_eq!(rules.min_removals(&[1, 9, 2, 9, 3, 9, 4]), 3);
+
+        let wide = Rules::new(1, i32::MAX, true, false, 0).unwrap();
+        assert_eq!(wide.min_removals(&[i32::MIN, 0, -5, -1]), 1);
+
+        let extremes = [i32::MAX, i32::MIN, i32::MIN + 2];
+        assert!(!is_safe(&extremes));
+        assert_eq!(
+            verdict(&extremes),
+            Verdict::SafeWithout {
+                index: 0,
+                level: i32::MAX,
+                direction: Direction::Increasing
+            }
+        );
+        assert_eq!(
+            Rules::new(-1, 3, true, true, 0),
+            Err(InvalidRules::NegativeStep)
+        );
+        assert_eq!(
+            Rules::new(3, 1, true, true, 0),
+            Err(InvalidRules::EmptySteps)
+        );
+    }
+
+    #[test]
+    fn explanations() {
+        let reports = read_reports("../fixtures/day02/example.txt");
+        assert_eq!(
+            Day02::explain(&reports).unwrap(),
+            [
+                "7 6 4 2 1: safe, decreasing",
+                "1 2 7 8 9: unsafe, 2 -> 7 at index 1 breaks the rules",
+                "9 7 6 2 1: unsafe, 6 -> 2 at index 2 breaks the rules",
+                "1 3 2 4 5: safe after removing level 3 at index 1, increasing",
+                "8 6 4 4 1: safe after removing level 4 at index 2, decreasing",
+                "1 3 6 7 9: safe, increasing",
+            ]
+        );
+    }
+}
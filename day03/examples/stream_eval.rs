@@ -0,0 +1,53 @@
+//! Evaluates day03's program from a file or stdin without reading it into
+//! memory first, printing the answers to both parts:
+//!
+//!     cargo run --release -p day03 --example stream_eval -- [--chunk <bytes>] [<file>]
+//!
+//! Reads stdin when no file is given. `--chunk` is the number of bytes read
+//! at a time.
+
+use day03::{eval_reader, Registry, State};
+use std::{fs::File, io, process::ExitCode};
+
+fn run() -> Result<State, String> {
+    let mut chunk_len = 1 << 16;
+    let mut input = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--chunk" => {
+                let n = args.next().ok_or("Missing chunk length")?;
+                chunk_len = n
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or(format!("Invalid chunk length: {}", n))?;
+            }
+            _ => input = Some(arg),
+        }
+    }
+
+    let registry = Registry::default();
+    let res = match &input {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            eval_reader(&registry, file, chunk_len)
+        }
+        None => eval_reader(&registry, io::stdin().lock(), chunk_len),
+    };
+    res.map_err(|e| format!("{}: {}", input.as_deref().unwrap_or("-"), e))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(state) => {
+            println!("{}", state.total);
+            println!("{}", state.enabled);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Splits corrupted memory into tokens, remembering where each one starts.
+//! Works on bytes, as memory dumps need not be valid UTF-8.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Letters, `'` and `_`, which may end in an instruction name.
+    Ident,
+    Number,
+    Open,
+    Close,
+    Comma,
+    /// Any other byte.
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: Kind,
+    pub text: &'a [u8],
+    /// Byte offset of the token in the input.
+    pub start: usize,
+}
+
+impl Token<'_> {
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    /// The text of an identifier or number, which is always ASCII.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(self.text).unwrap()
+    }
+}
+
+fn is_ident(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'\'' || c == b'_'
+}
+
+pub struct Lexer<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a [u8]) -> Lexer<'a> {
+        Lexer { input, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let rest = &self.input[self.pos..];
+        let run = |f: fn(u8) -> bool| rest.iter().position(|&c| !f(c)).unwrap_or(rest.len());
+        let (kind, len) = match *rest.first()? {
+            b'(' => (Kind::Open, 1),
+            b')' => (Kind::Close, 1),
+            b',' => (Kind::Comma, 1),
+            c if c.is_ascii_digit() => (Kind::Number, run(|c| c.is_ascii_digit())),
+            c if is_ident(c) => (Kind::Ident, run(is_ident)),
+            _ => (Kind::Other, 1),
+        };
+        let token = Token {
+            kind,
+            text: &rest[..len],
+            start: self.pos,
+        };
+        self.pos += len;
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let tokens: Vec<_> = Lexer::new("xmul(2,40)é[don't".as_bytes())
+            .map(|t| (t.kind, t.text, t.start))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Kind::Ident, &b"xmul"[..], 0),
+                (Kind::Open, b"(", 4),
+                (Kind::Number, b"2", 5),
+                (Kind::Comma, b",", 6),
+                (Kind::Number, b"40", 7),
+                (Kind::Close, b")", 9),
+                (Kind::Other, &[0xc3], 10),
+                (Kind::Other, &[0xa9], 11),
+                (Kind::Other, b"[", 12),
+                (Kind::Ident, b"don't", 13),
+            ]
+        );
+    }
+}
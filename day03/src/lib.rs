@@ -0,0 +1,223 @@
+mod lexer;
+mod parser;
+mod stream;
+
+use common::{ParseError, Solution};
+use std::ops::Range;
+
+pub use parser::{Op, Registry, Scan};
+pub use stream::{eval_reader, Stream};
+
+/// An argument of a call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Num(i64),
+    Call(Call),
+}
+
+/// A call of one of the registry's instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub name: &'static str,
+    pub args: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instr {
+    Do,
+    Dont,
+    Call(Call),
+}
+
+/// An instruction along with the bytes of the input it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned {
+    pub instr: Instr,
+    pub span: Range<usize>,
+}
+
+/// The result of running a program, with the spans of the calls that were
+/// executed and of those skipped because of a `don't()`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Evaluation {
+    pub value: i64,
+    pub executed: Vec<Range<usize>>,
+    pub skipped: Vec<Range<usize>>,
+}
+
+/// The state of an evaluation between two instructions, so a program can be
+/// evaluated as it arrives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// Sum of every call so far.
+    pub total: i64,
+    /// Sum of the calls made while running.
+    pub enabled: i64,
+    /// Unset after a `don't()`, until the next `do()`.
+    pub running: bool,
+}
+
+impl Default for State {
+    fn default() -> State {
+        State {
+            total: 0,
+            enabled: 0,
+            running: true,
+        }
+    }
+}
+
+impl State {
+    /// Runs `instr`, returning whether it was a call made while running, or
+    /// `None` for `do()` and `don't()`.
+    pub fn step(&mut self, registry: &Registry, instr: &Instr) -> Option<bool> {
+        match instr {
+            Instr::Do => self.running = true,
+            Instr::Dont => self.running = false,
+            Instr::Call(call) => {
+                let value = registry.value(call);
+                self.total += value;
+                if self.running {
+                    self.enabled += value;
+                }
+                return Some(self.running);
+            }
+        }
+        None
+    }
+}
+
+impl Registry {
+    /// The value of a call parsed with this registry.
+    pub fn value(&self, call: &Call) -> i64 {
+        let op = self
+            .op(call.name)
+            .expect("call of an unregistered instruction");
+        let args: Vec<i64> = call
+            .args
+            .iter()
+            .map(|arg| match arg {
+                Expr::Num(n) => *n,
+                Expr::Call(call) => self.value(call),
+            })
+            .collect();
+        (op.apply)(&args)
+    }
+
+    /// Sums the values of the calls in `instrs`. If `conditionals` is unset,
+    /// `do()` and `don't()` are ignored and every call is executed.
+    pub fn eval(&self, instrs: &[Spanned], conditionals: bool) -> Evaluation {
+        let mut evaluation = Evaluation::default();
+        let mut state = State::default();
+        for Spanned { instr, span } in instrs {
+            match state.step(self, instr) {
+                Some(true) => evaluation.executed.push(span.clone()),
+                Some(false) if !conditionals => evaluation.executed.push(span.clone()),
+                Some(false) => evaluation.skipped.push(span.clone()),
+                None => (),
+            }
+        }
+        evaluation.value = match conditionals {
+            true => state.enabled,
+            false => state.total,
+        };
+        evaluation
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Spanned>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Registry::default().parse(input))
+    }
+
+    fn part1(instrs: &Self::Input) -> i64 {
+        Registry::default().eval(instrs, false).value
+    }
+
+    fn part2(instrs: &Self::Input) -> i64 {
+        Registry::default().eval(instrs, true).value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    fn mul(a: i64, b: i64) -> Instr {
+        Instr::Call(Call {
+            name: "mul",
+            args: vec![Expr::Num(a), Expr::Num(b)],
+        })
+    }
+
+    #[test]
+    fn parses_with_offsets() {
+        let instrs = Registry::default().parse(EXAMPLE);
+        let spans: Vec<(Instr, &str)> = instrs
+            .into_iter()
+            .map(|s| (s.instr, &EXAMPLE[s.span]))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (mul(2, 4), "mul(2,4)"),
+                (Instr::Dont, "don't()"),
+                (mul(5, 5), "mul(5,5)"),
+                (mul(11, 8), "mul(11,8)"),
+                (Instr::Do, "do()"),
+                (mul(8, 5), "mul(8,5)"),
+            ]
+        );
+
+        let rejected = "mul(1234,5) mul(1,2,3) mul ( 2,4) mul(-2,4) mul(2,4";
+        assert_eq!(Registry::default().parse(rejected), []);
+        let nested = Registry::default().parse("mul(mul(2,3)");
+        assert_eq!(nested[0].instr, mul(2, 3));
+    }
+
+    #[test]
+    fn reports_skipped_spans() {
+        let registry = Registry::default();
+        let instrs = registry.parse(EXAMPLE);
+        let evaluation = registry.eval(&instrs, true);
+        assert_eq!(evaluation.value, 48);
+        assert_eq!(evaluation.executed, [1..9, 64..72]);
+        assert_eq!(evaluation.skipped, [28..36, 48..57]);
+        assert_eq!(registry.eval(&instrs, false).value, 161);
+    }
+
+    #[test]
+    fn extended_language() {
+        let registry = Registry::default()
+            .with("add", 2, |args| args[0] + args[1])
+            .with("sub", 2, |args| args[0] - args[1])
+            .with("neg", 1, |args| -args[0])
+            .max_digits(4)
+            .nested();
+        let input = "xadd(1000,2)don't()sub(1,2)do()mul(add(1,2),neg(sub(1,5)))%mul(add(1,),2)";
+        let instrs = registry.parse(input);
+        assert_eq!(instrs.len(), 5);
+        let evaluation = registry.eval(&instrs, true);
+        assert_eq!(evaluation.value, 1002 + 12);
+        let skipped: Vec<&str> = evaluation
+            .skipped
+            .iter()
+            .map(|s| &input[s.clone()])
+            .collect();
+        assert_eq!(skipped, ["sub(1,2)"]);
+        assert_eq!(
+            &input[evaluation.executed[1].clone()],
+            "mul(add(1,2),neg(sub(1,5)))"
+        );
+        assert_eq!(registry.eval(&instrs, false).value, 1002 - 1 + 12);
+    }
+}
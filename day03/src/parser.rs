@@ -0,0 +1,216 @@
+//! Picks the well-formed instructions out of the tokens, ignoring everything
+//! else.
+
+use crate::lexer::{Kind, Lexer, Token};
+use crate::{Call, Expr, Instr, Spanned};
+
+/// Why there is no instruction at some token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Miss {
+    NoMatch,
+    /// The tokens ran out before the instruction could be told apart from
+    /// garbage, which only matters if more input may follow.
+    Incomplete,
+}
+
+type Parsed<T> = Result<(T, usize), Miss>;
+
+/// The index after `tokens[i]` if it is of the given kind.
+fn expect(tokens: &[Token], i: usize, kind: Kind) -> Result<usize, Miss> {
+    match tokens.get(i) {
+        Some(t) if t.kind == kind => Ok(i + 1),
+        Some(_) => Err(Miss::NoMatch),
+        None => Err(Miss::Incomplete),
+    }
+}
+
+/// Instructions found in part of an input, and the byte offset where the
+/// input still needs to be looked at once more of it is known.
+pub struct Scan {
+    pub instrs: Vec<Spanned>,
+    pub rest: usize,
+}
+
+/// An instruction that computes a value from its arguments.
+#[derive(Debug, Clone, Copy)]
+pub struct Op {
+    pub name: &'static str,
+    pub arity: usize,
+    pub apply: fn(&[i64]) -> i64,
+}
+
+/// The instructions the parser recognizes, besides `do()` and `don't()`.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    ops: Vec<Op>,
+    max_digits: usize,
+    nested: bool,
+}
+
+impl Default for Registry {
+    /// The puzzle's language: `mul` of two numbers of up to 3 digits.
+    fn default() -> Registry {
+        Registry {
+            ops: Vec::new(),
+            max_digits: 3,
+            nested: false,
+        }
+        .with("mul", 2, |args| args[0] * args[1])
+    }
+}
+
+impl Registry {
+    /// Adds an instruction taking `arity` arguments.
+    pub fn with(mut self, name: &'static str, arity: usize, apply: fn(&[i64]) -> i64) -> Registry {
+        assert!(
+            !["do", "don't"].contains(&name) && self.op(name).is_none(),
+            "`{}` is already an instruction",
+            name
+        );
+        self.ops.push(Op { name, arity, apply });
+        self
+    }
+
+    pub fn max_digits(mut self, max_digits: usize) -> Registry {
+        self.max_digits = max_digits;
+        self
+    }
+
+    /// Allows calls as arguments, as in `mul(add(1,2),3)`.
+    pub fn nested(mut self) -> Registry {
+        self.nested = true;
+        self
+    }
+
+    pub fn op(&self, name: &str) -> Option<&Op> {
+        self.ops.iter().find(|op| op.name == name)
+    }
+
+    /// Finds every well-formed instruction in `input`.
+    pub fn parse(&self, input: &str) -> Vec<Spanned> {
+        self.scan(input.as_bytes(), true).instrs
+    }
+
+    /// Finds the well-formed instructions in `input`. Unless it is `last`,
+    /// more input may follow, so scanning stops at the first instruction that
+    /// could still be completed by it.
+    pub fn scan(&self, input: &[u8], last: bool) -> Scan {
+        let mut tokens: Vec<Token> = Lexer::new(input).collect();
+        // The last token may go on in the rest of the input.
+        let cut = match tokens.last() {
+            Some(&token) if !last => {
+                tokens.pop();
+                Some(token)
+            }
+            _ => None,
+        };
+        let names = self.names();
+        // Only the end of an identifier matters.
+        let keep = |ident: Token| ident.start.max(ident.end().saturating_sub(names[0].len()));
+
+        let mut instrs = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            match self.instr_at(&names, &tokens, i) {
+                Ok((instr, next)) => {
+                    instrs.push(instr);
+                    i = next;
+                }
+                Err(Miss::Incomplete) if !last => {
+                    let rest = keep(tokens[i]);
+                    return Scan { instrs, rest };
+                }
+                Err(_) => i += 1,
+            }
+        }
+        let rest = match cut {
+            Some(token) if token.kind == Kind::Ident => keep(token),
+            _ => input.len(),
+        };
+        Scan { instrs, rest }
+    }
+
+    /// Names that an identifier may end in, longest first, so that `xmul`
+    /// is read as `mul` and `undo` as `do`.
+    fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.ops.iter().map(|op| op.name).collect();
+        names.extend(["do", "don't"]);
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        names
+    }
+
+    /// The instruction whose name ends the identifier at `tokens[i]`, along
+    /// with the index of the token after it.
+    fn instr_at(&self, names: &[&'static str], tokens: &[Token], i: usize) -> Parsed<Spanned> {
+        let ident = tokens[i];
+        if ident.kind != Kind::Ident {
+            return Err(Miss::NoMatch);
+        }
+        for &name in names {
+            if !ident.text.ends_with(name.as_bytes()) {
+                continue;
+            }
+            let parsed = match name {
+                "do" => self
+                    .args(tokens, i + 1, 0)
+                    .map(|(_, next)| (Instr::Do, next)),
+                "don't" => self
+                    .args(tokens, i + 1, 0)
+                    .map(|(_, next)| (Instr::Dont, next)),
+                _ => self
+                    .parse_call(tokens, i, name)
+                    .map(|(call, next)| (Instr::Call(call), next)),
+            };
+            match parsed {
+                Ok((instr, next)) => {
+                    let start = ident.end() - name.len();
+                    let span = start..tokens[next - 1].end();
+                    return Ok((Spanned { instr, span }, next));
+                }
+                // A longer name takes precedence, so wait to see whether it
+                // matches.
+                Err(Miss::Incomplete) => return Err(Miss::Incomplete),
+                Err(Miss::NoMatch) => (),
+            }
+        }
+        Err(Miss::NoMatch)
+    }
+
+    fn parse_call(&self, tokens: &[Token], i: usize, name: &'static str) -> Parsed<Call> {
+        let op = self.op(name).ok_or(Miss::NoMatch)?;
+        let (args, next) = self.args(tokens, i + 1, op.arity)?;
+        Ok((Call { name, args }, next))
+    }
+
+    /// Parses `(arg, ...)` with exactly `arity` arguments from `tokens[i]`.
+    fn args(&self, tokens: &[Token], i: usize, arity: usize) -> Parsed<Vec<Expr>> {
+        let mut i = expect(tokens, i, Kind::Open)?;
+        let mut args = Vec::new();
+        for n in 0..arity {
+            if n > 0 {
+                i = expect(tokens, i, Kind::Comma)?;
+            }
+            let (arg, next) = self.arg(tokens, i)?;
+            args.push(arg);
+            i = next;
+        }
+        let i = expect(tokens, i, Kind::Close)?;
+        Ok((args, i))
+    }
+
+    fn arg(&self, tokens: &[Token], i: usize) -> Parsed<Expr> {
+        let token = tokens.get(i).ok_or(Miss::Incomplete)?;
+        match token.kind {
+            Kind::Number if token.text.len() <= self.max_digits => {
+                let n = token.as_str().parse().map_err(|_| Miss::NoMatch)?;
+                Ok((Expr::Num(n), i + 1))
+            }
+            Kind::Ident if self.nested => {
+                let op = self.op(token.as_str()).ok_or(Miss::NoMatch)?;
+                let (call, next) = self.parse_call(tokens, i, op.name)?;
+                Ok((Expr::Call(call), next))
+            }
+            _ => Err(Miss::NoMatch),
+        }
+    }
+}
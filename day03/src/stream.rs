@@ -0,0 +1,105 @@
+//! Evaluation of programs too large to hold in memory, fed a chunk at a time.
+
+use crate::{Registry, State};
+use std::io::{self, ErrorKind, Read};
+
+/// Evaluates a program as its bytes arrive. Only the tail of the input that
+/// may still hold the start of an instruction is kept between chunks.
+pub struct Stream<'r> {
+    registry: &'r Registry,
+    buf: Vec<u8>,
+    pub state: State,
+}
+
+impl<'r> Stream<'r> {
+    pub fn new(registry: &'r Registry) -> Stream<'r> {
+        Stream {
+            registry,
+            buf: Vec::new(),
+            state: State::default(),
+        }
+    }
+
+    /// Runs the instructions that `chunk` completes.
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+        self.run(false);
+    }
+
+    /// Runs what is left at the end of the input.
+    pub fn finish(mut self) -> State {
+        self.run(true);
+        self.state
+    }
+
+    fn run(&mut self, last: bool) {
+        let scan = self.registry.scan(&self.buf, last);
+        for spanned in &scan.instrs {
+            self.state.step(self.registry, &spanned.instr);
+        }
+        self.buf.drain(..scan.rest);
+    }
+}
+
+/// Evaluates the program read from `reader`, `chunk_len` bytes at a time.
+pub fn eval_reader(
+    registry: &Registry,
+    mut reader: impl Read,
+    chunk_len: usize,
+) -> io::Result<State> {
+    let mut stream = Stream::new(registry);
+    let mut chunk = vec![0; chunk_len];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) => return Ok(stream.finish()),
+            Ok(n) => stream.feed(&chunk[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_pieces(registry: &Registry, input: &[u8], len: usize) -> State {
+        let mut stream = Stream::new(registry);
+        for piece in input.chunks(len) {
+            stream.feed(piece);
+        }
+        stream.finish()
+    }
+
+    #[test]
+    fn split_instructions() {
+        let registry = Registry::default();
+        let input = b"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        for len in 1..=input.len() {
+            let state = in_pieces(&registry, input, len);
+            assert_eq!((state.total, state.enabled), (161, 48), "pieces of {}", len);
+        }
+        let state = eval_reader(&registry, &input[..], 4).unwrap();
+        assert_eq!((state.total, state.enabled, state.running), (161, 48, true));
+    }
+
+    #[test]
+    fn matches_whole_input() {
+        let registry = Registry::default()
+            .with("add", 2, |args| args[0] + args[1])
+            .nested();
+        let input = "mul(add(1,2),3)édon't()mul(2,2)do_not_mul(4,5)xxxxxxxxxmul(1000,1)\
+                     mul(add(mul(2,2),1),add(1,add(1,1)))mul(1,";
+        let whole = registry.eval(&registry.parse(input), true).value;
+        let all = registry.eval(&registry.parse(input), false).value;
+        for len in 1..=input.len() {
+            let state = in_pieces(&registry, input.as_bytes(), len);
+            assert_eq!(
+                (state.enabled, state.total),
+                (whole, all),
+                "pieces of {}",
+                len
+            );
+        }
+    }
+}
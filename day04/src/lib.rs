@@ -0,0 +1,106 @@
+mod lines;
+mod search;
+
+use common::{ParseError, Solution};
+use grid::Grid;
+
+pub use lines::find_lines;
+pub use search::{highlight, search, Mask, Match, Orientation, Shape};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part1(grid: &Self::Input) -> i32 {
+        find_lines(grid, &["XMAS"]).len() as i32
+    }
+
+    fn part2(grid: &Self::Input) -> i32 {
+        search(grid, &["MAS"], &[Shape::X]).len() as i32
+    }
+
+    /// The grid with only the letters of the matches of each part left.
+    fn explain(grid: &Self::Input) -> Option<Vec<String>> {
+        let parts = [
+            find_lines(grid, &["XMAS"]),
+            search(grid, &["MAS"], &[Shape::X]),
+        ];
+        let mut lines = Vec::new();
+        for matches in parts {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.extend(
+                highlight(grid, &matches)
+                    .to_string()
+                    .lines()
+                    .map(String::from),
+            );
+        }
+        Some(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::geom::Dir8;
+    use grid::Pos;
+
+    fn count_occurences(pos: Pos, word: &Vec<char>, grid: &Grid<char>) -> i32 {
+        let mut count = 0;
+        for dir in Dir8::ALL {
+            let mut list: Vec<char> = Vec::new();
+            for n in 0..word.len() as i32 {
+                if let Some(&c) = grid.get(pos + dir.delta() * n) {
+                    list.push(c);
+                }
+            }
+            if list == *word {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn has_x(pos: Pos, grid: &Grid<char>) -> bool {
+        let at = |dir: Dir8| grid.get(pos + dir.delta()).unwrap_or(&' ');
+        let d1 = vec![at(Dir8::UpLeft), at(Dir8::DownRight)];
+        let d2 = vec![at(Dir8::DownLeft), at(Dir8::UpRight)];
+        grid.get(pos) == Some(&'A')
+            && [d1, d2]
+                .iter()
+                .all(|d| *d == vec![&'M', &'S'] || *d == vec![&'S', &'M'])
+    }
+
+    fn example() -> Grid<char> {
+        Day04::parse(&std::fs::read_to_string("../fixtures/day04/example.txt").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn search_matches_reference() {
+        let grid = example();
+        let xmas: i32 = grid
+            .positions()
+            .map(|pos| count_occurences(pos, &vec!['X', 'M', 'A', 'S'], &grid))
+            .sum();
+        assert_eq!(Day04::part1(&grid), xmas);
+        let x_mas = grid.positions().filter(|&pos| has_x(pos, &grid)).count();
+        assert_eq!(Day04::part2(&grid), x_mas as i32);
+    }
+
+    #[test]
+    fn explanation() {
+        let lines = Day04::explain(&example()).unwrap();
+        assert_eq!(lines[0], "....XXMAS.");
+        assert_eq!(lines[10], "");
+        assert_eq!(lines[11], ".M.S......");
+    }
+}
@@ -0,0 +1,175 @@
+//! Fast path of `search` for straight lines: every row, column and diagonal is
+//! read once, in both directions, by an Aho-Corasick automaton matching all
+//! the words at the same time.
+
+use crate::{Match, Orientation};
+use common::geom::Dir8;
+use grid::{Grid, Pos};
+use std::collections::VecDeque;
+
+/// Automaton finding any of a set of words in a single pass over a text.
+struct AhoCorasick {
+    /// Transitions of each state of the trie of the words, sorted by letter.
+    next: Vec<Vec<(char, usize)>>,
+    /// The state for the longest proper suffix of each state that is also in
+    /// the trie.
+    fail: Vec<usize>,
+    /// The words ending at each state, including through its suffixes.
+    out: Vec<Vec<usize>>,
+}
+
+impl AhoCorasick {
+    fn new(words: &[Vec<char>]) -> AhoCorasick {
+        let mut ac = AhoCorasick {
+            next: vec![Vec::new()],
+            fail: vec![0],
+            out: vec![Vec::new()],
+        };
+        for (w, word) in words.iter().enumerate().filter(|(_, w)| !w.is_empty()) {
+            let mut state = 0;
+            for &c in word {
+                state = match ac.goto(state, c) {
+                    Some(next) => next,
+                    None => {
+                        let next = ac.next.len();
+                        let at = ac.next[state].partition_point(|&(d, _)| d < c);
+                        ac.next[state].insert(at, (c, next));
+                        ac.next.push(Vec::new());
+                        ac.fail.push(0);
+                        ac.out.push(Vec::new());
+                        next
+                    }
+                };
+            }
+            ac.out[state].push(w);
+        }
+
+        // Breadth first, so the suffixes of a state are done before it.
+        let mut queue: VecDeque<usize> = ac.next[0].iter().map(|&(_, s)| s).collect();
+        while let Some(state) = queue.pop_front() {
+            for (c, child) in ac.next[state].clone() {
+                let mut f = ac.fail[state];
+                while f != 0 && ac.goto(f, c).is_none() {
+                    f = ac.fail[f];
+                }
+                let fail = ac.goto(f, c).filter(|&s| s != child).unwrap_or(0);
+                ac.fail[child] = fail;
+                let inherited = ac.out[fail].clone();
+                ac.out[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        ac
+    }
+
+    fn goto(&self, state: usize, c: char) -> Option<usize> {
+        let next = &self.next[state];
+        next.binary_search_by_key(&c, |&(d, _)| d)
+            .ok()
+            .map(|i| next[i].1)
+    }
+
+    /// Calls `found` with the index of the last letter and the word for every
+    /// occurrence of a word in `text`.
+    fn find(&self, text: impl Iterator<Item = char>, mut found: impl FnMut(usize, usize)) {
+        let mut state = 0;
+        for (i, c) in text.enumerate() {
+            while state != 0 && self.goto(state, c).is_none() {
+                state = self.fail[state];
+            }
+            state = self.goto(state, c).unwrap_or(0);
+            for &w in &self.out[state] {
+                found(i, w);
+            }
+        }
+    }
+}
+
+/// The cells of every line of the grid going in `dir`.
+fn lines(grid: &Grid<char>, dir: Dir8) -> Vec<Vec<Pos>> {
+    grid.positions()
+        .filter(|&pos| !grid.contains(pos - dir.delta()))
+        .map(|start| {
+            let mut line = Vec::new();
+            let mut pos = start;
+            while grid.contains(pos) {
+                line.push(pos);
+                pos += dir.delta();
+            }
+            line
+        })
+        .collect()
+}
+
+/// Same as `search` with only `Shape::Line`, though the matches come in a
+/// different order.
+pub fn find_lines(grid: &Grid<char>, words: &[&str]) -> Vec<Match> {
+    let words: Vec<Vec<char>> = words.iter().map(|w| w.chars().collect()).collect();
+    let ac = AhoCorasick::new(&words);
+    let mut matches = Vec::new();
+    for dir in [Dir8::Right, Dir8::DownRight, Dir8::Down, Dir8::DownLeft] {
+        for mut line in lines(grid, dir) {
+            for dir in [dir, dir.opposite()] {
+                ac.find(line.iter().map(|&pos| grid[pos]), |end, w| {
+                    let cells = line[end + 1 - words[w].len()..=end].to_vec();
+                    matches.push(Match {
+                        word: w,
+                        shape: 0,
+                        start: cells[0],
+                        orientation: Orientation::Line(dir),
+                        cells,
+                    });
+                });
+                line.reverse();
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{search, Shape};
+    use common::Rng;
+
+    fn sorted(mut matches: Vec<Match>) -> Vec<Match> {
+        matches.sort_by_key(|m| (m.word, m.start, m.orientation));
+        matches
+    }
+
+    #[test]
+    fn automaton() {
+        let words: Vec<Vec<char>> = ["he", "she", "his", "hers"]
+            .iter()
+            .map(|w| w.chars().collect())
+            .collect();
+        let mut found = Vec::new();
+        AhoCorasick::new(&words).find("ushers".chars(), |end, w| found.push((end, w)));
+        assert_eq!(found, [(3, 1), (3, 0), (5, 3)]);
+    }
+
+    #[test]
+    fn matches_search() {
+        let mut rng = Rng::new(11);
+        for _ in 0..50 {
+            let (height, width) = (1 + rng.below(12) as usize, 1 + rng.below(12) as usize);
+            let grid = Grid::from_fn(height, width, |_| b"ABC"[rng.below(3) as usize] as char);
+            let words: Vec<String> = (0..1 + rng.below(6))
+                .map(|_| {
+                    (0..1 + rng.below(4))
+                        .map(|_| b"ABC"[rng.below(3) as usize] as char)
+                        .collect()
+                })
+                .collect();
+            let words: Vec<&str> = words.iter().map(String::as_str).collect();
+            assert_eq!(
+                sorted(find_lines(&grid, &words)),
+                sorted(search(&grid, &words, &[Shape::Line])),
+                "{:?} in\n{}",
+                words,
+                grid
+            );
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Word search for any number of words, laid out in lines, crosses or custom
+//! shapes.
+
+use common::{geom::Dir8, ParseError};
+use grid::{Grid, Pos};
+use std::str::FromStr;
+
+/// A custom shape: the cell of each letter of a word, given as a pattern with
+/// the letter's index (`0` for the first letter) in each used cell and `.`
+/// elsewhere. It matches in any rotation or reflection. Each index is a single
+/// digit, so a mask holds words of at most 10 letters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    /// Offset of each letter from the first one.
+    cells: Vec<Pos>,
+}
+
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Mask, ParseError> {
+        let mut cells: Vec<Option<Pos>> = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            for (i, c) in line.char_indices() {
+                let x = line[..i].chars().count();
+                let text = &line[i..i + c.len_utf8()];
+                let letter = match c {
+                    '.' | ' ' => continue,
+                    c => c.to_digit(10).ok_or_else(|| {
+                        ParseError::at(input, text, "expected a letter index or `.`")
+                    })? as usize,
+                };
+                if cells.len() <= letter {
+                    cells.resize(letter + 1, None);
+                }
+                if cells[letter].is_some() {
+                    return Err(ParseError::at(input, text, "letter used twice"));
+                }
+                cells[letter] = Some(Pos::new(x as i32, y as i32));
+            }
+        }
+        let Some(cells) = cells.into_iter().collect::<Option<Vec<Pos>>>() else {
+            return Err(ParseError::new(
+                "letter indices must go from 0 without gaps",
+            ));
+        };
+        match cells.first() {
+            Some(&first) => Ok(Mask {
+                cells: cells.iter().map(|&cell| cell - first).collect(),
+            }),
+            None => Err(ParseError::new("empty mask")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    /// The word in a straight line, in any of the 8 directions.
+    Line,
+    /// The word along both diagonals, crossing at its middle letter.
+    X,
+    /// The word along a row and a column, crossing at its middle letter.
+    Plus,
+    Mask(Mask),
+}
+
+/// Which way a match lies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Orientation {
+    Line(Dir8),
+    /// The directions in which the two arms of an `X` or `+` read.
+    Cross(Dir8, Dir8),
+    /// The mask turned clockwise by `turns` quarter turns, after being
+    /// mirrored left to right if `mirrored`.
+    Mask {
+        turns: u8,
+        mirrored: bool,
+    },
+}
+
+/// One way of placing a word: the offset of each of its letters from the
+/// first one.
+struct Placement {
+    orientation: Orientation,
+    cells: Vec<Pos>,
+}
+
+impl Shape {
+    fn placements(&self, len: usize) -> Vec<Placement> {
+        match self {
+            Shape::Line => Dir8::ALL
+                .iter()
+                .map(|&dir| Placement {
+                    orientation: Orientation::Line(dir),
+                    cells: (0..len as i32).map(|i| dir.delta() * i).collect(),
+                })
+                .collect(),
+            Shape::X => cross(
+                len,
+                [Dir8::DownRight, Dir8::UpLeft],
+                [Dir8::DownLeft, Dir8::UpRight],
+            ),
+            Shape::Plus => cross(len, [Dir8::Right, Dir8::Left], [Dir8::Down, Dir8::Up]),
+            Shape::Mask(mask) if mask.cells.len() == len => {
+                let mut placements: Vec<Placement> = Vec::new();
+                for mirrored in [false, true] {
+                    for turns in 0..4 {
+                        let cells = mask
+                            .cells
+                            .iter()
+                            .map(|&cell| {
+                                let cell = match mirrored {
+                                    true => Pos::new(-cell.x, cell.y),
+                                    false => cell,
+                                };
+                                (0..turns).fold(cell, |cell, _| cell.rotate_right())
+                            })
+                            .collect();
+                        // Symmetric masks look the same in several orientations.
+                        if placements.iter().all(|p| p.cells != cells) {
+                            let orientation = Orientation::Mask { turns, mirrored };
+                            placements.push(Placement { orientation, cells });
+                        }
+                    }
+                }
+                placements
+            }
+            Shape::Mask(_) => Vec::new(),
+        }
+    }
+}
+
+/// The word along two arms crossing at its middle letter, each arm reading in
+/// either of its directions. Words of even length have no middle letter.
+fn cross(len: usize, arm1: [Dir8; 2], arm2: [Dir8; 2]) -> Vec<Placement> {
+    if len.is_multiple_of(2) {
+        return Vec::new();
+    }
+    let half = len as i32 / 2;
+    let mut placements = Vec::new();
+    for d1 in arm1 {
+        for d2 in arm2 {
+            let center = d1.delta() * half;
+            let mut cells: Vec<Pos> = (0..len as i32).map(|i| d1.delta() * i).collect();
+            cells.extend((0..len as i32).map(|i| center + d2.delta() * (i - half)));
+            placements.push(Placement {
+                orientation: Orientation::Cross(d1, d2),
+                cells,
+            });
+        }
+    }
+    placements
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the word in the list searched for.
+    pub word: usize,
+    /// Index of the shape in the list searched for.
+    pub shape: usize,
+    /// Where the first letter is.
+    pub start: Pos,
+    pub orientation: Orientation,
+    /// The cells the match covers, some more than once for crosses.
+    pub cells: Vec<Pos>,
+}
+
+/// Finds every placement of every word in every shape. Empty words match
+/// nowhere.
+pub fn search(grid: &Grid<char>, words: &[&str], shapes: &[Shape]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for (w, word) in words.iter().enumerate().filter(|(_, w)| !w.is_empty()) {
+        let letters: Vec<char> = word.chars().collect();
+        for (s, shape) in shapes.iter().enumerate() {
+            for placement in shape.placements(letters.len()) {
+                // Crosses spell the word twice, hence the cycle.
+                let spells = |start: Pos| {
+                    placement
+                        .cells
+                        .iter()
+                        .zip(letters.iter().cycle())
+                        .all(|(&cell, letter)| grid.get(start + cell) == Some(letter))
+                };
+                matches.extend(
+                    grid.positions()
+                        .filter(|&pos| spells(pos))
+                        .map(|start| Match {
+                            word: w,
+                            shape: s,
+                            start,
+                            orientation: placement.orientation,
+                            cells: placement.cells.iter().map(|&cell| start + cell).collect(),
+                        }),
+                );
+            }
+        }
+    }
+    matches
+}
+
+/// The grid with every cell not covered by a match blanked out with `.`.
+pub fn highlight(grid: &Grid<char>, matches: &[Match]) -> Grid<char> {
+    let mut covered = Grid::new(grid.height(), grid.width(), false);
+    for cell in matches.iter().flat_map(|m| &m.cells) {
+        covered[*cell] = true;
+    }
+    Grid::from_fn(grid.height(), grid.width(), |pos| match covered[pos] {
+        true => grid[pos],
+        false => '.',
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<char> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn lines_and_crosses() {
+        let grid = grid("CAT.\nAAT.\nTAC.\n.T..");
+        let matches = search(&grid, &["CAT", "TA"], &[Shape::Line]);
+        let cats: Vec<(Pos, Orientation)> = matches
+            .iter()
+            .filter(|m| m.word == 0)
+            .map(|m| (m.start, m.orientation))
+            .collect();
+        assert_eq!(
+            cats,
+            [
+                (Pos::new(0, 0), Orientation::Line(Dir8::Right)),
+                (Pos::new(0, 0), Orientation::Line(Dir8::Down)),
+                (Pos::new(2, 2), Orientation::Line(Dir8::Left)),
+            ]
+        );
+        assert_eq!(matches.iter().filter(|m| m.word == 1).count(), 9);
+
+        assert_eq!(search(&grid, &["CAT"], &[Shape::X, Shape::Plus]), []);
+
+        let cross = self::grid(
+            "TOT
+OAO
+TOT",
+        );
+        let plus = search(&cross, &["OAO"], &[Shape::Plus]);
+        assert_eq!(plus.len(), 4);
+        assert_eq!(plus[0].start, Pos::new(0, 1));
+        assert_eq!(
+            plus[0].orientation,
+            Orientation::Cross(Dir8::Right, Dir8::Down)
+        );
+        assert_eq!(search(&cross, &["TAT", "OAT"], &[Shape::X]).len(), 4);
+        assert_eq!(search(&cross, &["TA"], &[Shape::X]), []);
+    }
+
+    #[test]
+    fn masks() {
+        let mask: Mask = "01\n.2".parse().unwrap();
+        let grid = grid("AB.\n.CB\n..A");
+        let matches = search(&grid, &["ABC"], &[Shape::Mask(mask)]);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            (matches[0].start, matches[0].orientation),
+            (
+                Pos::new(0, 0),
+                Orientation::Mask {
+                    turns: 0,
+                    mirrored: false
+                }
+            )
+        );
+        assert_eq!(matches[1].start, Pos::new(2, 2));
+        assert_eq!(highlight(&grid, &matches).to_string(), "AB.\n.CB\n..A");
+        assert_eq!(highlight(&grid, &matches[..1]).to_string(), "AB.\n.C.\n...");
+
+        assert_eq!(
+            "0.\n1x".parse::<Mask>().unwrap_err().to_string(),
+            "line 2, column 2: expected a letter index or `.`: `x`"
+        );
+        assert!("0.\n.2".parse::<Mask>().is_err());
+        assert!("00".parse::<Mask>().is_err());
+    }
+}
@@ -0,0 +1,180 @@
+mod order;
+mod validate;
+
+use common::{parse_num, ParseError, Solution};
+use std::collections::HashSet;
+
+pub use order::{find_cycle, order, Order};
+pub use validate::{validate, Move, Report, Violation};
+
+pub type Page = u32;
+pub type Rules = HashSet<(Page, Page)>;
+
+fn parse_input(input: &str) -> Result<(Rules, Vec<Vec<Page>>), ParseError> {
+    let Some((rules, updates)) = input.split_once("\n\n") else {
+        return Err(ParseError::new(
+            "expected rules and updates separated by a blank line",
+        ));
+    };
+
+    let rules: Vec<(Page, Page)> = rules
+        .split("\n")
+        .map(|l| match l.split_once("|") {
+            Some((a, b)) => Ok((parse_num(input, a.trim())?, parse_num(input, b.trim())?)),
+            None => Err(ParseError::at(input, l, "expected a rule like `47|53`")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut orders: Rules = HashSet::new();
+    for (l, r) in rules.iter() {
+        orders.insert((*l, *r));
+    }
+
+    let updates: Vec<Vec<Page>> = updates
+        .split("\n")
+        .map(|l| {
+            l.split(",")
+                .map(|p| parse_num(input, p))
+                .collect::<Result<Vec<Page>, _>>()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((orders, updates))
+}
+
+/// The pages of `update` in the order the rules put them in. If the rules
+/// allow several orders, the first one in lexicographic order is picked. If
+/// they form a cycle, there is no order and the cycle is returned instead.
+fn fix_order(update: &[Page], orders: &Rules) -> Result<Vec<Page>, Vec<Page>> {
+    match order(update, orders, 1) {
+        Order::Unique(pages) => Ok(pages),
+        Order::Ambiguous(mut pages) => Ok(pages.swap_remove(0)),
+        Order::Cycle(cycle) => Err(cycle),
+    }
+}
+
+/// Whether no rule puts a page of `update` before an earlier one. Rules
+/// through other pages of the update can't be broken without one of them
+/// being broken, so this agrees with `validate`, only cheaper.
+fn in_order(update: &[Page], orders: &Rules) -> bool {
+    update
+        .iter()
+        .enumerate()
+        .all(|(i, &a)| update[i + 1..].iter().all(|&b| !orders.contains(&(b, a))))
+}
+
+/// The rules of a cycle, as in `3|1, 1|2, 2|3`.
+fn show_cycle(cycle: &[Page]) -> String {
+    let rules: Vec<String> = cycle
+        .iter()
+        .zip(cycle.iter().cycle().skip(1))
+        .map(|(a, b)| format!("{}|{}", a, b))
+        .collect();
+    rules.join(", ")
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (Rules, Vec<Vec<Page>>);
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((orders, updates): &Self::Input) -> u32 {
+        let mut sum = 0;
+        for u in updates {
+            if in_order(u, orders) {
+                let mid = u[u.len() / 2];
+                sum += mid;
+            }
+        }
+        sum
+    }
+
+    fn part2((orders, updates): &Self::Input) -> u32 {
+        let mut sum2 = 0;
+        for u in updates {
+            if in_order(u, orders) {
+                continue;
+            }
+            // Updates whose rules form a cycle can't be fixed.
+            if let Ok(fixed) = fix_order(u, orders) {
+                let mid = fixed[fixed.len() / 2];
+                sum2 += mid;
+            }
+        }
+        sum2
+    }
+
+    /// The rules each update breaks and how to fix it, or the cycle that
+    /// makes it impossible.
+    fn explain((orders, updates): &Self::Input) -> Option<Vec<String>> {
+        let lines = updates.iter().map(|u| {
+            let pages: Vec<String> = u.iter().map(Page::to_string).collect();
+            let report = match validate(u, orders) {
+                Ok(report) => report,
+                Err(cycle) => {
+                    return format!(
+                        "{}: rules form a cycle: {}",
+                        pages.join(","),
+                        show_cycle(&cycle)
+                    )
+                }
+            };
+            if report.is_valid() {
+                return format!("{}: in order", pages.join(","));
+            }
+            let details: Vec<String> = report
+                .violations
+                .iter()
+                .map(Violation::to_string)
+                .chain(report.moves.iter().map(Move::to_string))
+                .collect();
+            format!("{}: {}", pages.join(","), details.join("; "))
+        });
+        Some(lines.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles() {
+        let input = parse_input("1|2\n2|3\n3|1\n\n1,2\n3,2,1\n2,1").unwrap();
+        assert_eq!(fix_order(&input.1[1], &input.0), Err(vec![3, 1, 2]));
+        assert_eq!(Day05::part1(&input), 2);
+        assert_eq!(Day05::part2(&input), 2);
+        assert_eq!(
+            Day05::explain(&input).unwrap(),
+            [
+                "1,2: in order",
+                "3,2,1: rules form a cycle: 3|1, 1|2, 2|3",
+                "2,1: 1|2 at positions 1 and 0; move 2 from 0 to 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn explanations() {
+        let input = std::fs::read_to_string("../fixtures/day05/example.txt").unwrap();
+        let (orders, updates) = parse_input(&input).unwrap();
+        for u in &updates {
+            assert_eq!(
+                in_order(u, &orders),
+                validate(u, &orders).unwrap().is_valid()
+            );
+        }
+        let lines = Day05::explain(&parse_input(&input).unwrap()).unwrap();
+        assert_eq!(lines[0], "75,47,61,53,29: in order");
+        assert_eq!(
+            lines[3],
+            "75,97,47,61,53: 97|75 at positions 1 and 0; move 75 from 0 to 1"
+        );
+    }
+}
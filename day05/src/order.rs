@@ -0,0 +1,179 @@
+//! Orders the pages of an update by the rules between them, as a topological
+//! sort of the graph with an edge `X -> Y` for every rule `X|Y` among them.
+
+use crate::{Page, Rules};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Order {
+    /// The rules allow a single order of the pages.
+    Unique(Vec<Page>),
+    /// The rules leave some pages unordered. Holds the valid orders, up to
+    /// the limit asked for.
+    Ambiguous(Vec<Vec<Page>>),
+    /// The rules contradict each other: each page must come before the next,
+    /// and the last one before the first.
+    Cycle(Vec<Page>),
+}
+
+/// For each page of the update, the indices of the pages that must come after
+/// it.
+pub(crate) fn graph(update: &[Page], rules: &Rules) -> Vec<Vec<usize>> {
+    update
+        .iter()
+        .map(|&a| {
+            (0..update.len())
+                .filter(|&j| rules.contains(&(a, update[j])))
+                .collect()
+        })
+        .collect()
+}
+
+/// A cycle in the rules between the pages of the update, if there is one.
+pub fn find_cycle(update: &[Page], rules: &Rules) -> Option<Vec<Page>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        New,
+        Open,
+        Done,
+    }
+
+    fn dfs(
+        i: usize,
+        after: &[Vec<usize>],
+        visits: &mut [Visit],
+        path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        visits[i] = Visit::Open;
+        path.push(i);
+        for &j in &after[i] {
+            match visits[j] {
+                Visit::Open => {
+                    let start = path.iter().position(|&k| k == j).unwrap();
+                    return Some(path[start..].to_vec());
+                }
+                Visit::New => {
+                    if let Some(cycle) = dfs(j, after, visits, path) {
+                        return Some(cycle);
+                    }
+                }
+                Visit::Done => (),
+            }
+        }
+        path.pop();
+        visits[i] = Visit::Done;
+        None
+    }
+
+    let after = graph(update, rules);
+    let mut visits = vec![Visit::New; update.len()];
+    (0..update.len()).find_map(|i| {
+        let cycle = match visits[i] {
+            Visit::New => dfs(i, &after, &mut visits, &mut Vec::new())?,
+            _ => return None,
+        };
+        Some(cycle.into_iter().map(|k| update[k]).collect())
+    })
+}
+
+/// Orders the pages of `update`, listing at most `limit` orders if there are
+/// several. They come in lexicographic order of the pages.
+pub fn order(update: &[Page], rules: &Rules, limit: usize) -> Order {
+    if let Some(cycle) = find_cycle(update, rules) {
+        return Order::Cycle(cycle);
+    }
+
+    let after = graph(update, rules);
+    let mut before = vec![0; update.len()];
+    for &j in after.iter().flatten() {
+        before[j] += 1;
+    }
+    // Enumerate at least two orders, to tell whether there is only one.
+    let mut orders = Vec::new();
+    enumerate(
+        update,
+        &after,
+        &mut before,
+        &mut Vec::new(),
+        &mut orders,
+        limit.max(2),
+    );
+    match orders.len() {
+        1 => Order::Unique(orders.pop().unwrap()),
+        _ => {
+            orders.truncate(limit);
+            Order::Ambiguous(orders)
+        }
+    }
+}
+
+/// Extends `prefix` in every possible way, picking any page that no page left
+/// must precede. In a graph without cycles there is always one, so every
+/// branch ends in a full order.
+fn enumerate(
+    update: &[Page],
+    after: &[Vec<usize>],
+    before: &mut [usize],
+    prefix: &mut Vec<usize>,
+    orders: &mut Vec<Vec<Page>>,
+    limit: usize,
+) {
+    if prefix.len() == update.len() {
+        orders.push(prefix.iter().map(|&i| update[i]).collect());
+        return;
+    }
+    let mut ready: Vec<usize> = (0..update.len())
+        .filter(|&i| before[i] == 0 && !prefix.contains(&i))
+        .collect();
+    ready.sort_by_key(|&i| update[i]);
+    for i in ready {
+        if orders.len() >= limit {
+            return;
+        }
+        for &j in &after[i] {
+            before[j] -= 1;
+        }
+        prefix.push(i);
+        enumerate(update, after, before, prefix, orders, limit);
+        prefix.pop();
+        for &j in &after[i] {
+            before[j] += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rules: &[(Page, Page)]) -> Rules {
+        rules.iter().copied().collect()
+    }
+
+    #[test]
+    fn orders() {
+        let chain = rules(&[(3, 1), (1, 2)]);
+        assert_eq!(order(&[1, 2, 3], &chain, 10), Order::Unique(vec![3, 1, 2]));
+
+        let fork = rules(&[(3, 1), (3, 2)]);
+        assert_eq!(
+            order(&[1, 2, 3], &fork, 10),
+            Order::Ambiguous(vec![vec![3, 1, 2], vec![3, 2, 1]])
+        );
+        assert_eq!(
+            order(&[1, 2, 3, 4], &fork, 3),
+            Order::Ambiguous(vec![vec![3, 1, 2, 4], vec![3, 1, 4, 2], vec![3, 2, 1, 4]])
+        );
+        assert_eq!(
+            order(&[1, 2, 3], &fork, 1),
+            Order::Ambiguous(vec![vec![3, 1, 2]])
+        );
+
+        let cycle = rules(&[(1, 2), (2, 3), (3, 1), (4, 1)]);
+        assert_eq!(
+            order(&[4, 2, 3, 1], &cycle, 10),
+            Order::Cycle(vec![1, 2, 3])
+        );
+        // Only the rules between the pages of the update matter.
+        assert_eq!(order(&[4, 1, 2], &cycle, 10), Order::Unique(vec![4, 1, 2]));
+    }
+}
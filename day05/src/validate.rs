@@ -0,0 +1,283 @@
+//! Explains what is wrong with an update: which rules it breaks, and the
+//! fewest pages to move to fix it.
+
+use crate::{find_cycle, order, order::graph, Order, Page, Rules};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+};
+
+/// A rule `X|Y` broken by `Y` coming before `X`, either directly or through
+/// other pages of the update (`X|A`, `A|B` and `B|Y` put `X` before `Y` too).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: (Page, Page),
+    /// Where `X` and `Y` are in the update.
+    pub positions: (usize, usize),
+    /// The pages in between for a transitive violation, in order.
+    pub via: Vec<Page>,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{} at positions {} and {}",
+            self.rule.0, self.rule.1, self.positions.0, self.positions.1
+        )?;
+        if !self.via.is_empty() {
+            let via: Vec<String> = self.via.iter().map(Page::to_string).collect();
+            write!(f, " via {}", via.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Moving `page` from position `from` to position `to` of the fixed update.
+/// Positions can be equal when pages before it move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub page: Page,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.page, self.from, self.to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub violations: Vec<Violation>,
+    /// As few moves as possible that make the update follow the rules.
+    pub moves: Vec<Move>,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// The pages strictly between `from` and `to` on a shortest path of rules,
+/// if there is one.
+fn path(update: &[Page], after: &[Vec<usize>], from: usize, to: usize) -> Option<Vec<Page>> {
+    let mut prev = vec![None; update.len()];
+    let mut queue = VecDeque::from([from]);
+    while let Some(i) = queue.pop_front() {
+        for &j in &after[i] {
+            if prev[j].is_none() && j != from {
+                prev[j] = Some(i);
+                queue.push_back(j);
+            }
+        }
+    }
+    let mut via = Vec::new();
+    let mut i = prev[to]?;
+    while i != from {
+        via.push(update[i]);
+        i = prev[i]?;
+    }
+    via.reverse();
+    Some(via)
+}
+
+/// Checks every pair of pages of `update`, or returns the pages of a cycle in
+/// its rules, which can't be followed.
+pub fn validate(update: &[Page], rules: &Rules) -> Result<Report, Vec<Page>> {
+    if let Some(cycle) = find_cycle(update, rules) {
+        return Err(cycle);
+    }
+    let n = update.len();
+    let after = graph(update, rules);
+    // Transitive closure: `precedes[i][j]` if page `i` must come before `j`.
+    let mut precedes = vec![vec![false; n]; n];
+    for (i, after) in after.iter().enumerate() {
+        for &j in after {
+            precedes[i][j] = true;
+        }
+    }
+    for k in 0..n {
+        let through = precedes[k].clone();
+        for row in precedes.iter_mut().filter(|row| row[k]) {
+            for (p, &t) in row.iter_mut().zip(&through) {
+                *p |= t;
+            }
+        }
+    }
+
+    let mut violations = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            if let Some(via) = path(update, &after, j, i) {
+                violations.push(Violation {
+                    rule: (update[j], update[i]),
+                    positions: (j, i),
+                    via,
+                });
+            }
+        }
+    }
+    let moves = match violations.is_empty() {
+        true => Vec::new(),
+        false => moves(update, &after, &precedes),
+    };
+    Ok(Report { violations, moves })
+}
+
+/// The pages that can stay where they are are the largest set without a
+/// violation among them. Violations order the pages (by position, and against
+/// the rules), so that is the largest antichain of this order, found from a
+/// maximum matching by Dilworth's and König's theorems.
+fn moves(update: &[Page], after: &[Vec<usize>], precedes: &[Vec<bool>]) -> Vec<Move> {
+    let n = update.len();
+    let conflicts: Vec<Vec<usize>> = (0..n)
+        .map(|i| (i + 1..n).filter(|&j| precedes[j][i]).collect())
+        .collect();
+
+    // Kuhn's algorithm, matching each page to a later one it conflicts with.
+    fn augment(
+        i: usize,
+        conflicts: &[Vec<usize>],
+        matched: &mut [Option<usize>],
+        seen: &mut [bool],
+    ) -> bool {
+        for &j in &conflicts[i] {
+            if !seen[j] {
+                seen[j] = true;
+                if matched[j].is_none_or(|k| augment(k, conflicts, matched, seen)) {
+                    matched[j] = Some(i);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    let mut matched = vec![None; n];
+    let left_matched: Vec<bool> = (0..n)
+        .map(|i| augment(i, &conflicts, &mut matched, &mut vec![false; n]))
+        .collect();
+
+    // Pages reachable from the unmatched ones by alternating paths.
+    let mut left_seen = vec![false; n];
+    let mut right_seen = vec![false; n];
+    let mut stack: Vec<usize> = (0..n).filter(|&i| !left_matched[i]).collect();
+    while let Some(i) = stack.pop() {
+        if std::mem::replace(&mut left_seen[i], true) {
+            continue;
+        }
+        for &j in &conflicts[i] {
+            if !right_seen[j] {
+                right_seen[j] = true;
+                if let Some(k) = matched[j] {
+                    stack.push(k);
+                }
+            }
+        }
+    }
+    let stays: Vec<usize> = (0..n).filter(|&i| left_seen[i] && !right_seen[i]).collect();
+
+    // Any order following the rules between the pages and keeping the staying
+    // pages in place relative to each other.
+    let mut fixing: Rules = after
+        .iter()
+        .enumerate()
+        .flat_map(|(i, after)| after.iter().map(move |&j| (update[i], update[j])))
+        .collect();
+    fixing.extend(stays.windows(2).map(|w| (update[w[0]], update[w[1]])));
+    let fixed = match order(update, &fixing, 1) {
+        Order::Unique(pages) => pages,
+        Order::Ambiguous(mut pages) => pages.swap_remove(0),
+        Order::Cycle(_) => unreachable!("the staying pages don't conflict"),
+    };
+    (0..n)
+        .filter(|i| !stays.contains(i))
+        .map(|from| Move {
+            page: update[from],
+            from,
+            to: fixed.iter().position(|&p| p == update[from]).unwrap(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Rng;
+
+    fn rules(rules: &[(Page, Page)]) -> Rules {
+        rules.iter().copied().collect()
+    }
+
+    #[test]
+    fn violations() {
+        let rules = rules(&[(1, 2), (2, 3), (4, 5)]);
+        let report = validate(&[3, 2, 1, 4, 5], &rules).unwrap();
+        let violations: Vec<String> = report.violations.iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            violations,
+            [
+                "2|3 at positions 1 and 0",
+                "1|3 at positions 2 and 0 via 2",
+                "1|2 at positions 2 and 1",
+            ]
+        );
+        assert_eq!(report.moves.len(), 2);
+        assert!(validate(&[1, 3, 4, 5], &rules).unwrap().is_valid());
+
+        let cycle: Rules = [(1, 2), (2, 1)].into_iter().collect();
+        assert_eq!(validate(&[1, 2], &cycle), Err(vec![1, 2]));
+    }
+
+    /// Moves the pages and checks the result against the rules.
+    fn apply(update: &[Page], moves: &[Move]) -> Vec<Page> {
+        let mut fixed: Vec<Option<Page>> = vec![None; update.len()];
+        for m in moves {
+            fixed[m.to] = Some(m.page);
+        }
+        let mut staying = update
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| moves.iter().all(|m| m.from != *i));
+        for slot in fixed.iter_mut().filter(|slot| slot.is_none()) {
+            *slot = staying.next().map(|(_, &p)| p);
+        }
+        fixed.into_iter().map(Option::unwrap).collect()
+    }
+
+    #[test]
+    fn fewest_moves() {
+        let mut rng = Rng::new(5);
+        for _ in 0..300 {
+            let n = 1 + rng.below(8) as usize;
+            let mut update: Vec<Page> = (0..n as Page).collect();
+            for i in (1..n).rev() {
+                update.swap(i, rng.below(i as u64 + 1) as usize);
+            }
+            // Rules only from smaller to larger pages, so there are no cycles.
+            let rules: Rules = (0..n as Page)
+                .flat_map(|a| (a + 1..n as Page).map(move |b| (a, b)))
+                .filter(|_| rng.below(3) == 0)
+                .collect();
+            let report = validate(&update, &rules).unwrap();
+
+            // The largest set of pages without a violation among them.
+            let stays = (0u32..1 << n)
+                .filter(|set| {
+                    report
+                        .violations
+                        .iter()
+                        .all(|v| set & (1 << v.positions.0) == 0 || set & (1 << v.positions.1) == 0)
+                })
+                .map(|set| set.count_ones() as usize)
+                .max()
+                .unwrap();
+            assert_eq!(report.moves.len(), n - stays, "{:?} {:?}", update, rules);
+            assert!(validate(&apply(&update, &report.moves), &rules)
+                .unwrap()
+                .is_valid());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Walks every guard of a map, drawn as `^`, `v`, `<` or `>`, and prints what
+//! happens to each:
+//!
+//!     cargo run -p day06 --example patrol -- [--turn <right|left|alternating>] [--wrap] <file>
+//!
+//! `--wrap` makes guards walking off an edge come back in from the opposite
+//! one.
+
+use day06::{parse_guards, patrol, Fate, Rules, Turn};
+use std::process::ExitCode;
+
+fn run() -> Result<(), String> {
+    let mut rules = Rules::default();
+    let mut input = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--turn" => {
+                rules.turn = match args.next().as_deref() {
+                    Some("right") => Turn::Right,
+                    Some("left") => Turn::Left,
+                    Some("alternating") => Turn::Alternating,
+                    Some(turn) => return Err(format!("Invalid turn: {}", turn)),
+                    None => return Err("Missing turn".to_string()),
+                }
+            }
+            "--wrap" => rules.wrap = true,
+            _ => input = Some(arg),
+        }
+    }
+
+    let path = input.ok_or("Missing input file")?;
+    let text =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (grid, guards) = parse_guards(&text).map_err(|e| format!("{}: {}", path, e))?;
+    let patrol = patrol(&grid, &guards, &rules);
+    for (i, guard) in guards.iter().enumerate() {
+        let fate = match patrol.fate(i) {
+            Fate::Exits(time) => format!("leaves the map at time {}", time),
+            Fate::Loops => format!("loops every {} steps", patrol.routes[i].cycle().len()),
+            Fate::Collides(c) => {
+                let other = if c.guards.0 == i {
+                    c.guards.1
+                } else {
+                    c.guards.0
+                };
+                format!(
+                    "collides with guard {} at {},{} at time {}",
+                    other, c.pos.x, c.pos.y, c.time
+                )
+            }
+        };
+        println!(
+            "Guard {} ({} at {},{}) {}",
+            i,
+            guard.dir.arrow(),
+            guard.pos.x,
+            guard.pos.y,
+            fate
+        );
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Draws the guard's route on a day06 map, optionally with an added obstacle:
+//!
+//!     cargo run -p day06 --example route -- [--obstacle <x>,<y>] [--animate] [--delay <ms>] <file>
+//!
+//! `--animate` redraws the map after every move of the guard, waiting
+//! `--delay` milliseconds (default 50) between frames.
+
+use common::Solution;
+use day06::{frames, render, walk, Day06};
+use grid::Pos;
+use std::{process::ExitCode, thread, time::Duration};
+
+fn parse_pos(s: &str) -> Option<Pos> {
+    let (x, y) = s.split_once(',')?;
+    Some(Pos::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+fn run() -> Result<(), String> {
+    let mut obstacle = None;
+    let mut animate = false;
+    let mut delay = 50;
+    let mut input = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--obstacle" => {
+                let pos = args.next().ok_or("Missing obstacle position")?;
+                obstacle = Some(parse_pos(&pos).ok_or(format!("Invalid position: {}", pos))?);
+            }
+            "--animate" => animate = true,
+            "--delay" => {
+                let ms = args.next().ok_or("Missing delay")?;
+                delay = ms.parse().map_err(|_| format!("Invalid delay: {}", ms))?;
+            }
+            _ => input = Some(arg),
+        }
+    }
+
+    let path = input.ok_or("Missing input file")?;
+    let text =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (mut grid, guard) = Day06::parse(&text).map_err(|e| format!("{}: {}", path, e))?;
+    let map = grid.clone();
+    if let Some(obstacle) = obstacle {
+        if grid.get(obstacle) != Some(&'.') || obstacle == guard.pos {
+            return Err(format!(
+                "Can't put an obstacle at {},{}",
+                obstacle.x, obstacle.y
+            ));
+        }
+        grid[obstacle] = '#';
+    }
+
+    let route = walk(&grid, guard.pos, guard.dir);
+    if animate {
+        for frame in frames(&map, &route.states, obstacle) {
+            // Clear the terminal and go back to its top left corner.
+            println!("\x1b[2J\x1b[H{}", frame);
+            thread::sleep(Duration::from_millis(delay));
+        }
+    } else {
+        println!("{}", render(&map, &route.states, obstacle));
+    }
+    match route.cycle_start {
+        Some(_) => println!("Loop of {} states", route.cycle().len()),
+        None => println!("Leaves the map after {} states", route.states.len()),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Loop detection that moves the guard from obstacle to obstacle instead of
+//! cell by cell.
+
+use common::geom::Dir4;
+use grid::{Grid, Pos};
+
+/// Marks a guard walking off the map.
+const EXIT: u32 = u32::MAX;
+
+/// For every cell and direction, the cell where a guard walking that way stops
+/// in front of an obstacle.
+#[derive(Clone)]
+pub struct JumpTable {
+    width: usize,
+    height: usize,
+    blocked: Vec<bool>,
+    stops: Vec<[u32; 4]>,
+}
+
+/// Table entries changed by `add_obstacle`, to undo it.
+pub struct Changes {
+    obstacle: usize,
+    entries: Vec<(usize, usize, u32)>,
+}
+
+impl JumpTable {
+    pub fn new(grid: &Grid<char>) -> JumpTable {
+        let mut table = JumpTable {
+            width: grid.width(),
+            height: grid.height(),
+            blocked: grid.iter().map(|(_, &c)| c == '#').collect(),
+            stops: vec![[EXIT; 4]; grid.width() * grid.height()],
+        };
+        for dir in Dir4::ALL {
+            // Walk back from the edge the guard would leave by, remembering
+            // where the last obstacle seen stops it.
+            let edge: Vec<Pos> = grid
+                .positions()
+                .filter(|&pos| !grid.contains(pos + dir.delta()))
+                .collect();
+            for mut pos in edge {
+                let mut stop = EXIT;
+                while let Some(i) = table.index(pos) {
+                    if table.blocked[i] {
+                        stop = table.index(pos - dir.delta()).map_or(EXIT, |i| i as u32);
+                    } else {
+                        table.stops[i][dir as usize] = stop;
+                    }
+                    pos -= dir.delta();
+                }
+            }
+        }
+        table
+    }
+
+    pub fn index(&self, pos: Pos) -> Option<usize> {
+        let in_grid =
+            (0..self.width as i32).contains(&pos.x) && (0..self.height as i32).contains(&pos.y);
+        in_grid.then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Where a guard at cell `i` facing `dir` stops, or `None` if it walks off
+    /// the map.
+    pub fn stop(&self, i: usize, dir: Dir4) -> Option<usize> {
+        match self.stops[i][dir as usize] {
+            EXIT => None,
+            stop => Some(stop as usize),
+        }
+    }
+
+    /// Puts an obstacle at `pos`, which only changes where the guards walking
+    /// into it from each side stop.
+    pub fn add_obstacle(&mut self, pos: Pos) -> Changes {
+        let obstacle = self.index(pos).expect("obstacle outside the map");
+        self.blocked[obstacle] = true;
+        // Nothing stands on an obstacle, so its own entries are unused.
+        let mut entries: Vec<_> = (0..4)
+            .map(|dir| (obstacle, dir, self.stops[obstacle][dir]))
+            .collect();
+        self.stops[obstacle] = [EXIT; 4];
+        for dir in Dir4::ALL {
+            let mut behind = pos - dir.delta();
+            let Some(stop) = self.index(behind) else {
+                continue;
+            };
+            while let Some(i) = self.index(behind).filter(|&i| !self.blocked[i]) {
+                let entry = &mut self.stops[i][dir as usize];
+                entries.push((i, dir as usize, *entry));
+                *entry = stop as u32;
+                behind -= dir.delta();
+            }
+        }
+        Changes { obstacle, entries }
+    }
+
+    #[cfg(test)]
+    pub fn stops(&self) -> &[[u32; 4]] {
+        &self.stops
+    }
+
+    pub fn undo(&mut self, changes: Changes) {
+        self.blocked[changes.obstacle] = false;
+        for (i, dir, stop) in changes.entries.into_iter().rev() {
+            self.stops[i][dir] = stop;
+        }
+    }
+
+    /// Whether a guard at `pos` facing `dir` ends up walking in circles.
+    /// `seen` is reused from one trial to the next to save allocations.
+    pub fn loops(&self, pos: Pos, mut dir: Dir4, seen: &mut Visits) -> bool {
+        seen.next_trial(self.stops.len());
+        let mut i = self.index(pos).unwrap();
+        while let Some(stop) = self.stop(i, dir) {
+            i = stop;
+            dir = dir.turn_right();
+            if !seen.insert(i * 4 + dir as usize) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// States seen in the current trial, stamped with the trial number so they
+/// don't need clearing between trials.
+#[derive(Default)]
+pub struct Visits {
+    trial: u32,
+    stamps: Vec<u32>,
+}
+
+impl Visits {
+    fn next_trial(&mut self, cells: usize) {
+        self.stamps.resize(cells * 4, 0);
+        self.trial += 1;
+    }
+
+    /// Returns `false` if the state was already seen in this trial.
+    fn insert(&mut self, state: usize) -> bool {
+        std::mem::replace(&mut self.stamps[state], self.trial) != self.trial
+    }
+}
@@ -0,0 +1,235 @@
+mod jump;
+mod patrol;
+mod route;
+
+use common::{ParseError, Solution};
+use grid::{Grid, Pos};
+use std::{collections::HashSet, thread};
+
+pub use jump::{Changes, JumpTable, Visits};
+pub use patrol::{parse_guards, patrol, Collision, Fate, Guard, Patrol, Rules, Turn};
+pub use route::{frames, render, walk, walk_with, Route};
+
+/// The cells where an obstacle makes the guard walk in circles, in the order
+/// it reaches them. Until it first reaches such a cell, the guard walks its
+/// usual route, so each trial starts right in front of the new obstacle.
+/// Trials are spread over threads, each with its own copy of the jump table.
+pub fn loop_obstacles(guard: Guard, grid: &Grid<char>) -> Vec<Pos> {
+    let steps = walk(grid, guard.pos, guard.dir).first_steps();
+    let table = JumpTable::new(grid);
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_len = steps.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let workers: Vec<_> = steps
+            .chunks(chunk_len)
+            .map(|chunk| {
+                let mut table = table.clone();
+                scope.spawn(move || {
+                    let mut seen = Visits::default();
+                    chunk
+                        .iter()
+                        .filter(|&&(pos, dir)| {
+                            let changes = table.add_obstacle(pos + dir.delta());
+                            let loops = table.loops(pos, dir, &mut seen);
+                            table.undo(changes);
+                            loops
+                        })
+                        .map(|&(pos, dir)| pos + dir.delta())
+                        .collect::<Vec<Pos>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    })
+}
+
+/// The route of the guard with each obstacle of `loop_obstacles`, whose
+/// `cycle` is the loop it creates.
+pub fn looping_routes(guard: Guard, grid: &Grid<char>) -> Vec<(Pos, Route)> {
+    loop_obstacles(guard, grid)
+        .into_iter()
+        .map(|obstacle| {
+            let mut with_obstacle = grid.clone();
+            with_obstacle[obstacle] = '#';
+            (obstacle, walk(&with_obstacle, guard.pos, guard.dir))
+        })
+        .collect()
+}
+
+/// The puzzle's map, which has a single guard.
+fn parse(input: &str) -> Result<(Grid<char>, Guard), ParseError> {
+    let (grid, guards) = parse_guards(input)?;
+    match guards[..] {
+        [guard] => Ok((grid, guard)),
+        [] => Err(ParseError::new("no guard found")),
+        [_, second, ..] => {
+            let line = input.lines().nth(second.pos.y as usize).unwrap();
+            let x = second.pos.x as usize;
+            Err(ParseError::at(
+                input,
+                &line[x..x + 1],
+                "more than one guard",
+            ))
+        }
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = (Grid<char>, Guard);
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((grid, guard): &Self::Input) -> usize {
+        let route = walk(grid, guard.pos, guard.dir);
+        route
+            .states
+            .iter()
+            .map(|&(pos, _)| pos)
+            .collect::<HashSet<Pos>>()
+            .len()
+    }
+
+    fn part2((grid, guard): &Self::Input) -> usize {
+        loop_obstacles(*guard, grid).len()
+    }
+
+    /// The guard's route, then the loop each obstacle of part 2 creates.
+    fn explain((grid, guard): &Self::Input) -> Option<Vec<String>> {
+        let route = walk(grid, guard.pos, guard.dir);
+        let mut lines: Vec<String> = render(grid, &route.states, None)
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect();
+        lines.push(String::new());
+        for (obstacle, route) in looping_routes(*guard, grid) {
+            let cycle = Route {
+                states: route.cycle().to_vec(),
+                cycle_start: Some(0),
+            };
+            let turns: Vec<String> = cycle
+                .turns()
+                .iter()
+                .map(|pos| format!("{},{}", pos.x, pos.y))
+                .collect();
+            lines.push(format!(
+                "O at {},{}: loop of {} states turning at {}",
+                obstacle.x,
+                obstacle.y,
+                cycle.states.len(),
+                turns.join(" ")
+            ));
+        }
+        Some(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{geom::Dir4, Rng};
+
+    /// Walks the guard cell by cell, as a reference for the jump table.
+    fn find_exit_pt2(pos: &Pos, dir: Dir4, grid: &Grid<char>) -> Result<(), ()> {
+        let mut pos = *pos;
+        let mut dir = dir;
+        let mut visited: HashSet<(Pos, Dir4)> = HashSet::new();
+        loop {
+            let next_pos = pos + dir.delta();
+            match grid.get(next_pos) {
+                Some('#') => {
+                    dir = dir.turn_right();
+                    if visited.contains(&(pos, dir)) {
+                        // Loop detected.
+                        return Ok(());
+                    }
+                    visited.insert((pos, dir));
+                }
+                None => {
+                    // Out of bounds.
+                    return Err(());
+                }
+                Some(_) => {
+                    pos = next_pos;
+                }
+            }
+        }
+    }
+
+    /// Checks every obstacle the guard could run into against the reference.
+    fn check(grid: &Grid<char>, guard: Guard) {
+        let steps = walk(grid, guard.pos, guard.dir).first_steps();
+        let mut table = JumpTable::new(grid);
+        let mut seen = Visits::default();
+        let mut expected = 0;
+        for (pos, dir) in steps {
+            let obstacle = pos + dir.delta();
+            let mut with_obstacle = grid.clone();
+            with_obstacle[obstacle] = '#';
+            let loops = find_exit_pt2(&guard.pos, guard.dir, &with_obstacle).is_ok();
+            expected += loops as usize;
+            assert_eq!(walk(&with_obstacle, guard.pos, guard.dir).loops(), loops);
+
+            let changes = table.add_obstacle(obstacle);
+            assert_eq!(JumpTable::new(&with_obstacle).stops(), table.stops());
+            assert_eq!(
+                table.loops(pos, dir, &mut seen),
+                loops,
+                "obstacle at {:?}",
+                obstacle
+            );
+            table.undo(changes);
+        }
+        assert_eq!(JumpTable::new(grid).stops(), table.stops());
+        assert_eq!(loop_obstacles(guard, grid).len(), expected);
+    }
+
+    #[test]
+    fn single_guard() {
+        let (_, guard) = parse("..\n.<").unwrap();
+        assert_eq!(guard.dir, Dir4::Left);
+        assert_eq!(
+            parse("v.\n.<").unwrap_err().to_string(),
+            "line 2, column 2: more than one guard: `<`"
+        );
+        assert!(parse("..").is_err());
+    }
+
+    #[test]
+    fn jump_table_matches_walking() {
+        let input = std::fs::read_to_string("../fixtures/day06/example.txt").unwrap();
+        let (grid, guard) = parse(&input).unwrap();
+        check(&grid, guard);
+
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let (height, width) = (1 + rng.below(15) as usize, 1 + rng.below(15) as usize);
+            let mut grid = Grid::from_fn(height, width, |_| match rng.below(6) {
+                0 => '#',
+                _ => '.',
+            });
+            let start = Pos::new(
+                rng.below(width as u64) as i32,
+                rng.below(height as u64) as i32,
+            );
+            grid[start] = '.';
+            let guard = Guard {
+                pos: start,
+                dir: Dir4::ALL[rng.below(4) as usize],
+            };
+            // Guards already walking in circles have no way out to count from.
+            if !JumpTable::new(&grid).loops(guard.pos, guard.dir, &mut Visits::default()) {
+                check(&grid, guard);
+            }
+        }
+    }
+}
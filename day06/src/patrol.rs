@@ -0,0 +1,357 @@
+//! Any number of guards patrolling the same map at once, with other turning
+//! rules and maps that wrap around.
+
+use crate::{walk_with, Route};
+use common::{geom::Dir4, ParseError};
+use grid::{Grid, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Guard {
+    pub pos: Pos,
+    pub dir: Dir4,
+}
+
+/// Which way a guard turns in front of an obstacle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Turn {
+    #[default]
+    Right,
+    Left,
+    /// Right the first time, then left, then right again and so on.
+    Alternating,
+}
+
+impl Turn {
+    /// The direction after turning, for a guard that turned `turns` times
+    /// before.
+    pub fn apply(self, dir: Dir4, turns: usize) -> Dir4 {
+        match self {
+            Turn::Right => dir.turn_right(),
+            Turn::Left => dir.turn_left(),
+            Turn::Alternating if turns.is_multiple_of(2) => dir.turn_right(),
+            Turn::Alternating => dir.turn_left(),
+        }
+    }
+}
+
+/// How guards move. The default is the puzzle's: turning right, and leaving
+/// the map at its edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rules {
+    pub turn: Turn,
+    /// Whether walking off an edge brings the guard back in from the opposite
+    /// one.
+    pub wrap: bool,
+}
+
+/// Parses a map with any number of guards drawn as `^`, `v`, `<` or `>`, and
+/// returns it without them, along with the guards in reading order.
+pub fn parse_guards(input: &str) -> Result<(Grid<char>, Vec<Guard>), ParseError> {
+    let mut grid = Grid::parse_allowing(input, "#.^v<>")?;
+    let guards: Vec<Guard> = grid
+        .iter()
+        .filter_map(|(pos, &c)| {
+            Some(Guard {
+                pos,
+                dir: Dir4::from_arrow(c)?,
+            })
+        })
+        .collect();
+    for guard in &guards {
+        grid[guard.pos] = '.';
+    }
+    Ok((grid, guards))
+}
+
+/// Two guards walking into the same cell at the same time, or passing each
+/// other by swapping cells, or a guard walking into one that stopped before.
+/// They stop there, and stay in the way of the guards still walking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collision {
+    pub guards: (usize, usize),
+    /// The number of moves and turns made before colliding.
+    pub time: usize,
+    /// Where the first guard collides: the cell it walks into when passing the
+    /// other.
+    pub pos: Pos,
+}
+
+/// What happens to a guard in the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fate {
+    /// Walks off the map after that many moves and turns.
+    Exits(usize),
+    Loops,
+    Collides(Collision),
+}
+
+pub struct Patrol {
+    /// The route of each guard as if it were alone on the map, including the
+    /// part after it collides: `fate` tells where it stopped.
+    pub routes: Vec<Route>,
+    /// In the order they happen. A guard that stopped can be in several, the
+    /// first being the one that stopped it.
+    pub collisions: Vec<Collision>,
+}
+
+impl Patrol {
+    pub fn fate(&self, guard: usize) -> Fate {
+        let collision = self
+            .collisions
+            .iter()
+            .find(|c| c.guards.0 == guard || c.guards.1 == guard);
+        let route = &self.routes[guard];
+        match (collision, route.loops()) {
+            (Some(&collision), _) => Fate::Collides(collision),
+            (None, true) => Fate::Loops,
+            (None, false) => Fate::Exits(route.states.len()),
+        }
+    }
+}
+
+/// Where the guard following `route` is at `time`, if still on the map.
+fn pos_at(route: &Route, time: usize) -> Option<Pos> {
+    let len = route.states.len();
+    let i = match route.cycle_start {
+        _ if time < len => time,
+        Some(start) => start + (time - start) % (len - start),
+        None => return None,
+    };
+    Some(route.states[i].0)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+/// A guard's route, and the time and cell it stopped in if it collided.
+struct Track<'a> {
+    route: &'a Route,
+    stop: Option<(usize, Pos)>,
+}
+
+/// How a track goes on: it leaves the map at some time, or its positions
+/// repeat with some period from some time on.
+enum Horizon {
+    Ends(usize),
+    Repeats { from: usize, period: usize },
+}
+
+impl Track<'_> {
+    fn pos_at(&self, time: usize) -> Option<Pos> {
+        match self.stop {
+            Some((stop, pos)) if time >= stop => Some(pos),
+            _ => pos_at(self.route, time),
+        }
+    }
+
+    fn horizon(&self) -> Horizon {
+        match (self.stop, self.route.cycle_start) {
+            (Some((stop, _)), _) => Horizon::Repeats {
+                from: stop,
+                period: 1,
+            },
+            (None, Some(start)) => Horizon::Repeats {
+                from: start,
+                period: self.route.cycle().len(),
+            },
+            (None, None) => Horizon::Ends(self.route.states.len()),
+        }
+    }
+}
+
+/// The first collision between the guards on tracks `a` and `b`, if they
+/// were the only ones.
+fn first_collision(a: &Track, b: &Track) -> Option<(usize, Pos)> {
+    // Once both repeat, their positions repeat together.
+    let end = match (a.horizon(), b.horizon()) {
+        (Horizon::Ends(ea), Horizon::Ends(eb)) => ea.min(eb),
+        (Horizon::Ends(end), _) | (_, Horizon::Ends(end)) => end,
+        (
+            Horizon::Repeats {
+                from: fa,
+                period: pa,
+            },
+            Horizon::Repeats {
+                from: fb,
+                period: pb,
+            },
+        ) => fa.max(fb) + pa / gcd(pa, pb) * pb,
+    };
+    (0..end).find_map(|time| {
+        let (pa, pb) = (a.pos_at(time)?, b.pos_at(time)?);
+        if pa == pb {
+            return Some((time, pa));
+        }
+        let (na, nb) = (a.pos_at(time + 1)?, b.pos_at(time + 1)?);
+        (na == pb && nb == pa).then_some((time + 1, na))
+    })
+}
+
+/// Walks every guard until it leaves the map, collides with another guard or
+/// walks in circles. Guards don't block each other while walking: they only
+/// collide, and the ones that collided block the others from then on.
+pub fn patrol(grid: &Grid<char>, guards: &[Guard], rules: &Rules) -> Patrol {
+    let routes: Vec<Route> = guards
+        .iter()
+        .map(|&guard| walk_with(grid, guard, rules))
+        .collect();
+    let mut tracks: Vec<Track> = routes
+        .iter()
+        .map(|route| Track { route, stop: None })
+        .collect();
+
+    // Each round takes the earliest collisions between the tracks so far, and
+    // stops the guards in them, which changes the tracks after that time.
+    let mut collisions = Vec::new();
+    loop {
+        let mut candidates: Vec<Collision> = Vec::new();
+        for a in 0..tracks.len() {
+            for b in a + 1..tracks.len() {
+                if tracks[a].stop.is_some() && tracks[b].stop.is_some() {
+                    continue;
+                }
+                if let Some((time, pos)) = first_collision(&tracks[a], &tracks[b]) {
+                    candidates.push(Collision {
+                        guards: (a, b),
+                        time,
+                        pos,
+                    });
+                }
+            }
+        }
+        let Some(time) = candidates.iter().map(|c| c.time).min() else {
+            break;
+        };
+        // Several guards can meet at once.
+        candidates.retain(|c| c.time == time);
+        for c in &candidates {
+            for g in [c.guards.0, c.guards.1] {
+                if tracks[g].stop.is_none() {
+                    let pos = tracks[g].pos_at(time).unwrap();
+                    tracks[g].stop = Some((time, pos));
+                }
+            }
+        }
+        collisions.extend(candidates);
+    }
+    Patrol { routes, collisions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fates(input: &str, rules: Rules) -> Vec<Fate> {
+        let (grid, guards) = parse_guards(input).unwrap();
+        let patrol = patrol(&grid, &guards, &rules);
+        (0..guards.len()).map(|g| patrol.fate(g)).collect()
+    }
+
+    #[test]
+    fn turning_rules() {
+        let map = "..#..\n.....\n..^..\n.....\n.....";
+        let right = Rules::default();
+        assert_eq!(fates(map, right), [Fate::Exits(5)]);
+        let (grid, guards) = parse_guards(map).unwrap();
+        let route = walk_with(&grid, guards[0], &right);
+        assert_eq!(route.states.last(), Some(&(Pos::new(4, 1), Dir4::Right)));
+
+        let left = Rules {
+            turn: Turn::Left,
+            ..right
+        };
+        let route = walk_with(&grid, guards[0], &left);
+        assert_eq!(route.states.last(), Some(&(Pos::new(0, 1), Dir4::Left)));
+
+        // Right at the first obstacle, then left at the second one.
+        let stairs = ".....\n#....\n...#.\n.....\n^....";
+        let alternating = Rules {
+            turn: Turn::Alternating,
+            ..right
+        };
+        let (grid, guards) = parse_guards(stairs).unwrap();
+        let route = walk_with(&grid, guards[0], &alternating);
+        assert_eq!(route.turns(), [Pos::new(0, 2), Pos::new(2, 2)]);
+        assert_eq!(route.states.last(), Some(&(Pos::new(2, 0), Dir4::Up)));
+        assert_eq!(fates(stairs, alternating), [Fate::Exits(9)]);
+    }
+
+    #[test]
+    fn wrapping() {
+        let wrap = Rules {
+            wrap: true,
+            ..Rules::default()
+        };
+        assert_eq!(fates("...\n.^.\n...", wrap), [Fate::Loops]);
+        let (grid, guards) = parse_guards("...\n.^.\n...").unwrap();
+        assert_eq!(walk_with(&grid, guards[0], &wrap).cycle().len(), 3);
+        assert_eq!(fates("...\n.^.\n...", Rules::default()), [Fate::Exits(2)]);
+    }
+
+    #[test]
+    fn collisions() {
+        let fates = |input| fates(input, Rules::default());
+        // Head on, passing each other between two cells.
+        let swap = Collision {
+            guards: (0, 1),
+            time: 1,
+            pos: Pos::new(2, 0),
+        };
+        assert_eq!(fates(".><."), [Fate::Collides(swap); 2]);
+        let meet = Collision {
+            guards: (0, 1),
+            time: 1,
+            pos: Pos::new(1, 0),
+        };
+        assert_eq!(fates(">.<"), [Fate::Collides(meet); 2]);
+
+        // The first guard would have run into the third, but it stopped, and
+        // the third then runs into both.
+        let (first, second) = (
+            Collision {
+                guards: (0, 2),
+                time: 3,
+                pos: Pos::new(1, 0),
+            },
+            Collision {
+                guards: (1, 2),
+                time: 3,
+                pos: Pos::new(1, 0),
+            },
+        );
+        assert_eq!(
+            fates(">.<.<"),
+            [
+                Fate::Collides(meet),
+                Fate::Collides(meet),
+                Fate::Collides(first)
+            ]
+        );
+        let (grid, guards) = parse_guards(">.<.<").unwrap();
+        let patrol = patrol(&grid, &guards, &Rules::default());
+        assert_eq!(patrol.collisions, [meet, first, second]);
+    }
+
+    #[test]
+    fn collisions_with_stopped_guards() {
+        // The third guard walks in circles down its column, and runs into the
+        // first two once they stopped in its way.
+        let wrap = Rules {
+            wrap: true,
+            ..Rules::default()
+        };
+        let circling = Collision {
+            guards: (0, 2),
+            time: 2,
+            pos: Pos::new(2, 0),
+        };
+        assert_eq!(
+            fates(".>.<.\n..v..\n.....", wrap)[2],
+            Fate::Collides(circling)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! The guard's route step by step, and drawings of it in the style of the
+//! puzzle text.
+
+use crate::{Guard, Rules, Turn};
+use common::geom::Dir4;
+use grid::{Grid, Pos};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// Every state of the guard in order: its cell and the way it faces.
+    /// Turning adds a state in the same cell.
+    pub states: Vec<(Pos, Dir4)>,
+    /// For a guard walking in circles, the index of the first state that
+    /// repeats.
+    pub cycle_start: Option<usize>,
+}
+
+impl Route {
+    pub fn loops(&self) -> bool {
+        self.cycle_start.is_some()
+    }
+
+    /// The states the guard goes through again and again, empty if it walks
+    /// off the map.
+    pub fn cycle(&self) -> &[(Pos, Dir4)] {
+        match self.cycle_start {
+            Some(start) => &self.states[start..],
+            None => &[],
+        }
+    }
+
+    /// The cells where the guard turns, in order. A loop ends with the turn
+    /// back into its first state, if there is one.
+    pub fn turns(&self) -> Vec<Pos> {
+        let next = self.states[1..]
+            .iter()
+            .chain(self.cycle_start.map(|i| &self.states[i]));
+        let mut turns: Vec<Pos> = self
+            .states
+            .iter()
+            .zip(next)
+            .filter(|(a, b)| a.1 != b.1)
+            .map(|(a, _)| a.0)
+            .collect();
+        // Turning back the way it came takes two turns in the same cell.
+        turns.dedup();
+        turns
+    }
+
+    /// Where the guard first walks into each cell of the route, as the cell it
+    /// comes from and its direction. The cell it starts in isn't included, as
+    /// it is already standing there.
+    pub fn first_steps(&self) -> Vec<(Pos, Dir4)> {
+        let mut seen = HashSet::from([self.states[0].0]);
+        let mut steps = Vec::new();
+        for w in self.states.windows(2) {
+            if w[0].0 != w[1].0 && seen.insert(w[1].0) {
+                steps.push(w[0]);
+            }
+        }
+        steps
+    }
+}
+
+/// Walks the guard cell by cell from `start` until it leaves the map or gets
+/// back to a state it was in.
+pub fn walk(grid: &Grid<char>, start: Pos, dir: Dir4) -> Route {
+    walk_with(grid, Guard { pos: start, dir }, &Rules::default())
+}
+
+/// Same as `walk` for any turning rule and map edges. On a map that wraps
+/// around, the guard always ends up walking in circles.
+pub fn walk_with(grid: &Grid<char>, guard: Guard, rules: &Rules) -> Route {
+    let size = Pos::new(grid.width() as i32, grid.height() as i32);
+    let Guard { mut pos, mut dir } = guard;
+    let mut turns = 0;
+    let mut states = Vec::new();
+    // Alternating turns make the next turn part of the state.
+    let mut index: HashMap<(Pos, Dir4, bool), usize> = HashMap::new();
+    loop {
+        let key = (pos, dir, rules.turn == Turn::Alternating && turns % 2 == 1);
+        if let Some(&i) = index.get(&key) {
+            return Route {
+                states,
+                cycle_start: Some(i),
+            };
+        }
+        index.insert(key, states.len());
+        states.push((pos, dir));
+        let mut next_pos = pos + dir.delta();
+        if rules.wrap {
+            next_pos = next_pos.rem_euclid(size);
+        }
+        match grid.get(next_pos) {
+            Some('#') => {
+                dir = rules.turn.apply(dir, turns);
+                turns += 1;
+            }
+            None => {
+                return Route {
+                    states,
+                    cycle_start: None,
+                }
+            }
+            Some(_) => pos = next_pos,
+        }
+    }
+}
+
+/// Marks which ways the guard has crossed each cell, drawing `|`, `-` or `+`.
+struct Canvas {
+    grid: Grid<char>,
+    crossed: Grid<(bool, bool)>,
+}
+
+impl Canvas {
+    fn new(grid: &Grid<char>, obstacle: Option<Pos>) -> Canvas {
+        let mut grid = grid.clone();
+        if let Some(obstacle) = obstacle {
+            grid[obstacle] = 'O';
+        }
+        let crossed = Grid::new(grid.height(), grid.width(), (false, false));
+        Canvas { grid, crossed }
+    }
+
+    fn mark(&mut self, (pos, dir): (Pos, Dir4)) {
+        let crossed = &mut self.crossed[pos];
+        match dir {
+            Dir4::Up | Dir4::Down => crossed.0 = true,
+            Dir4::Left | Dir4::Right => crossed.1 = true,
+        }
+        self.grid[pos] = match *crossed {
+            (true, true) => '+',
+            (true, false) => '|',
+            _ => '-',
+        };
+    }
+}
+
+/// Draws `states` on the grid as in the puzzle text: `|` and `-` where the
+/// guard walks up or down and left or right, `+` where it does both (as when
+/// it turns), and `O` for the added `obstacle`. The first state is drawn as
+/// the guard's arrow.
+pub fn render(grid: &Grid<char>, states: &[(Pos, Dir4)], obstacle: Option<Pos>) -> Grid<char> {
+    let mut canvas = Canvas::new(grid, obstacle);
+    for &state in states {
+        canvas.mark(state);
+    }
+    if let Some(&(pos, dir)) = states.first() {
+        canvas.grid[pos] = dir.arrow();
+    }
+    canvas.grid
+}
+
+/// The drawing after each state in turn, with the guard's arrow where it
+/// stands, to play as an animation.
+pub fn frames<'a>(
+    grid: &Grid<char>,
+    states: &'a [(Pos, Dir4)],
+    obstacle: Option<Pos>,
+) -> impl Iterator<Item = Grid<char>> + 'a {
+    let mut canvas = Canvas::new(grid, obstacle);
+    states.iter().map(move |&(pos, dir)| {
+        canvas.mark((pos, dir));
+        let mut frame = canvas.grid.clone();
+        frame[pos] = dir.arrow();
+        frame
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn draws_the_puzzle_loops() {
+        let input = std::fs::read_to_string("../fixtures/day06/example.txt").unwrap();
+        let (grid, guard) = parse(&input).unwrap();
+        let route = walk(&grid, guard.pos, guard.dir);
+        assert!(!route.loops());
+        assert_eq!(route.turns().len(), 10);
+        assert_eq!(
+            frames(&grid, &route.states, None).last().unwrap()[Pos::new(7, 9)],
+            'v'
+        );
+
+        let obstacle = Pos::new(3, 6);
+        let mut with_obstacle = grid.clone();
+        with_obstacle[obstacle] = '#';
+        let route = walk(&with_obstacle, guard.pos, guard.dir);
+        assert!(route.loops());
+        assert_eq!(
+            route.turns(),
+            [
+                Pos::new(4, 1),
+                Pos::new(8, 1),
+                Pos::new(8, 6),
+                Pos::new(4, 6)
+            ]
+        );
+        assert_eq!(
+            render(&grid, &route.states, Some(obstacle)).to_string(),
+            "....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#..."
+        );
+        assert_eq!(route.cycle().len(), route.states.len());
+    }
+}
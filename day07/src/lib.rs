@@ -0,0 +1,225 @@
+mod ops;
+mod solve;
+
+use common::{ParseError, Solution};
+use std::{
+    fmt::{self, Display},
+    num::{IntErrorKind, ParseIntError},
+};
+
+pub use ops::{
+    digits, Add, Concat, Max, Min, Mul, NoValue, Operator, Overflow, Pow, Registry, Sub, Undo, Xor,
+};
+
+#[derive(Debug)]
+pub struct Test {
+    pub test_val: u64,
+    pub operands: Vec<u64>,
+}
+
+/// A number of the input, which must fit in a `u64`.
+fn parse_num(input: &str, text: &str) -> Result<u64, ParseError> {
+    text.parse().map_err(|e: ParseIntError| match e.kind() {
+        IntErrorKind::PosOverflow => ParseError::at(input, text, "number too large for 64 bits"),
+        _ => ParseError::at(input, text, "invalid number"),
+    })
+}
+
+fn parse(contents: &str) -> Result<Vec<Test>, ParseError> {
+    let mut tests = Vec::new();
+    for line in contents.lines() {
+        let Some((test_val, operands)) = line.split_once(":") else {
+            return Err(ParseError::at(
+                contents,
+                line,
+                "expected `<value>: <operands>`",
+            ));
+        };
+        let t = Test {
+            test_val: parse_num(contents, test_val)?,
+            operands: operands
+                .split(" ")
+                .filter(|x| !x.is_empty())
+                .map(|x| parse_num(contents, x))
+                .collect::<Result<_, _>>()?,
+        };
+        if t.operands.is_empty() {
+            return Err(ParseError::at(contents, line, "no operands"));
+        }
+        tests.push(t);
+    }
+    Ok(tests)
+}
+
+/// The answer to a part: the sum of the values of the equations that can be
+/// true, or the first equation that can't be told because of an overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Total {
+    /// A `u128`, so that it can't overflow.
+    Sum(u128),
+    Overflow {
+        /// Counting from 0.
+        equation: usize,
+        overflow: Overflow,
+    },
+}
+
+impl Display for Total {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Total::Sum(sum) => write!(f, "{}", sum),
+            Total::Overflow { equation, overflow } => {
+                write!(f, "equation {}: {}", equation + 1, overflow)
+            }
+        }
+    }
+}
+
+fn calibration_sum(tests: &[Test], registry: &Registry) -> Total {
+    let mut sum = 0;
+    for (equation, t) in tests.iter().enumerate() {
+        match registry.solve(t.test_val, &t.operands) {
+            Ok(Some(_)) => sum += t.test_val as u128,
+            Ok(None) => (),
+            Err(overflow) => return Total::Overflow { equation, overflow },
+        }
+    }
+    Total::Sum(sum)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Test>;
+    type Output1 = Total;
+    type Output2 = Total;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(tests: &Self::Input) -> Total {
+        calibration_sum(tests, &Registry::default())
+    }
+
+    fn part2(tests: &Self::Input) -> Total {
+        calibration_sum(tests, &Registry::default().with(Concat))
+    }
+
+    /// The operators of each equation that can be true, with those of part 2.
+    fn explain(tests: &Self::Input) -> Option<Vec<String>> {
+        let registry = Registry::default().with(Concat);
+        let lines = tests
+            .iter()
+            .map(|t| match registry.solve(t.test_val, &t.operands) {
+                Ok(Some(ops)) => registry.show(t.test_val, &t.operands, &ops),
+                Ok(None) => format!("{}: impossible", t.test_val),
+                Err(overflow) => format!("{}: {}", t.test_val, overflow),
+            });
+        Some(lines.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Rng;
+
+    /// Every value up to `u64::MAX` the operands can take, trying every
+    /// combination of operators, as a reference for `solve`.
+    fn values(registry: &Registry, operands: &[u64]) -> Vec<u128> {
+        let n = registry.ops().len();
+        let combinations = n.pow(operands.len() as u32 - 1);
+        (0..combinations)
+            .filter_map(|mut c| {
+                let ops: Vec<usize> = (1..operands.len())
+                    .map(|_| {
+                        let op = c % n;
+                        c /= n;
+                        op
+                    })
+                    .collect();
+                registry.eval(operands, &ops).ok().flatten()
+            })
+            .filter(|&v| v <= u64::MAX as u128)
+            .collect()
+    }
+
+    #[test]
+    fn solve_matches_enumeration() {
+        let mut rng = Rng::new(7);
+        let registries = [
+            Registry::default(),
+            Registry::default().with(Concat),
+            Registry::default()
+                .with(Concat)
+                .with(Sub)
+                .with(Xor)
+                .with(Min)
+                .with(Max)
+                .with(Pow),
+        ];
+        for _ in 0..300 {
+            let operands: Vec<u64> = (0..1 + rng.below(5)).map(|_| rng.below(30)).collect();
+            for registry in &registries {
+                let values = values(registry, &operands);
+                // Mostly values that work, and some that don't.
+                let value = match rng.below(4) {
+                    0 => rng.below(1000),
+                    _ => values[rng.below(values.len() as u64) as usize] as u64,
+                };
+                match registry.solve(value, &operands).ok().flatten() {
+                    Some(found) => assert_eq!(
+                        registry.eval(&operands, &found),
+                        Ok(Some(value as u128)),
+                        "{}",
+                        registry.show(value, &operands, &found)
+                    ),
+                    None => assert!(
+                        !values.contains(&(value as u128)),
+                        "{} {:?}",
+                        value,
+                        operands
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_numbers() {
+        let input = "18446744073709551615: 1 8446744073709551615\n18446744073709551616: 1 2";
+        assert_eq!(
+            parse(input).unwrap_err().to_string(),
+            "line 2, column 1: number too large for 64 bits: `18446744073709551616`"
+        );
+        let tests = parse(
+            "18446744073709551615: 1 8446744073709551615
+18446744073709551614: 9223372036854775807 2
+0: 18446744073709551615 18446744073709551615 0",
+        )
+        .unwrap();
+        assert_eq!(Day07::part1(&tests), Total::Sum(18446744073709551614));
+        assert_eq!(Day07::part2(&tests), Total::Sum(36893488147419103229));
+        assert_eq!(
+            Day07::explain(&tests).unwrap()[2],
+            "0: 18446744073709551615 + 18446744073709551615 * 0"
+        );
+
+        let total = calibration_sum(&tests[2..], &Registry::empty().with(Mul));
+        assert_eq!(total, Total::Sum(0));
+        let tests =
+            parse("0: 18446744073709551615 18446744073709551615 18446744073709551615 0").unwrap();
+        let total = calibration_sum(&tests, &Registry::empty().with(Mul));
+        assert_eq!(total.to_string(), "equation 1: overflow at operator 2");
+    }
+
+    #[test]
+    fn explanations() {
+        let input = std::fs::read_to_string("../fixtures/day07/example.txt").unwrap();
+        let lines = Day07::explain(&parse(&input).unwrap()).unwrap();
+        assert_eq!(lines[0], "190: 10 * 19");
+        assert_eq!(lines[1], "3267: 81 * 40 + 27");
+        assert_eq!(lines[2], "83: impossible");
+    }
+}
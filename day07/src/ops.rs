@@ -0,0 +1,358 @@
+//! The operators equations can use. Arithmetic is done on `u128`, so the
+//! results of 64-bit operands have room to grow, and is checked: a result past
+//! `u128::MAX` is an overflow, reported rather than wrapped.
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+/// The number of decimal digits of `n`, with `0` having one.
+pub fn digits(n: u128) -> u32 {
+    n.checked_ilog10().map_or(1, |d| d + 1)
+}
+
+/// Why an operator gives no value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoValue {
+    /// There is no such value, as when subtracting past zero.
+    Undefined,
+    /// The value is larger than `u128::MAX`.
+    Overflow,
+}
+
+/// The left operands that give some value, for working an equation backwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Undo {
+    None,
+    One(u128),
+    /// Every left operand in the range gives the value.
+    Range(RangeInclusive<u128>),
+    /// The left operand would be larger than `u128::MAX`.
+    Overflow,
+    /// The operator can't tell, so every value of the left side must be tried.
+    Unknown,
+}
+
+const ANY: RangeInclusive<u128> = 0..=u128::MAX;
+
+pub trait Operator {
+    /// How the operator is written in equations, as in `+`.
+    fn symbol(&self) -> &'static str;
+
+    /// `a` and `b` combined.
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue>;
+
+    /// The left operands `a` for which `apply(a, b)` is `value`. Operators
+    /// that don't say are solved by trying every left operand.
+    fn undo(&self, _value: u128, _b: u128) -> Undo {
+        Undo::Unknown
+    }
+}
+
+impl From<Option<u128>> for Undo {
+    fn from(a: Option<u128>) -> Undo {
+        a.map_or(Undo::None, Undo::One)
+    }
+}
+
+pub struct Add;
+
+impl Operator for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        a.checked_add(b).ok_or(NoValue::Overflow)
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        value.checked_sub(b).into()
+    }
+}
+
+pub struct Mul;
+
+impl Operator for Mul {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        a.checked_mul(b).ok_or(NoValue::Overflow)
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        match (value, b) {
+            (0, 0) => Undo::Range(ANY),
+            (_, 0) => Undo::None,
+            _ => value.is_multiple_of(b).then(|| value / b).into(),
+        }
+    }
+}
+
+/// Appends the digits of `b` to `a`.
+pub struct Concat;
+
+impl Operator for Concat {
+    fn symbol(&self) -> &'static str {
+        "||"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        let shifted = match 10_u128.checked_pow(digits(b)) {
+            Some(shift) => a.checked_mul(shift),
+            None => (a == 0).then_some(0),
+        };
+        shifted
+            .and_then(|s| s.checked_add(b))
+            .ok_or(NoValue::Overflow)
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        match 10_u128.checked_pow(digits(b)) {
+            Some(shift) => (value % shift == b).then(|| value / shift).into(),
+            // Only `0 || b` fits.
+            None => (value == b).then_some(0).into(),
+        }
+    }
+}
+
+pub struct Sub;
+
+impl Operator for Sub {
+    fn symbol(&self) -> &'static str {
+        "-"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        a.checked_sub(b).ok_or(NoValue::Undefined)
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        value.checked_add(b).map_or(Undo::Overflow, Undo::One)
+    }
+}
+
+pub struct Xor;
+
+impl Operator for Xor {
+    fn symbol(&self) -> &'static str {
+        "^"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        Ok(a ^ b)
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        Undo::One(value ^ b)
+    }
+}
+
+pub struct Min;
+
+impl Operator for Min {
+    fn symbol(&self) -> &'static str {
+        "min"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        Ok(a.min(b))
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        match value.cmp(&b) {
+            Ordering::Less => Undo::One(value),
+            Ordering::Equal => Undo::Range(b..=u128::MAX),
+            Ordering::Greater => Undo::None,
+        }
+    }
+}
+
+pub struct Max;
+
+impl Operator for Max {
+    fn symbol(&self) -> &'static str {
+        "max"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        Ok(a.max(b))
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        match value.cmp(&b) {
+            Ordering::Less => Undo::None,
+            Ordering::Equal => Undo::Range(0..=b),
+            Ordering::Greater => Undo::One(value),
+        }
+    }
+}
+
+/// `a` to the power of `b`.
+pub struct Pow;
+
+impl Operator for Pow {
+    fn symbol(&self) -> &'static str {
+        "**"
+    }
+
+    fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+        match (a, u32::try_from(b)) {
+            (0 | 1, Err(_)) => Ok(a),
+            (_, Err(_)) => Err(NoValue::Overflow),
+            (_, Ok(b)) => a.checked_pow(b).ok_or(NoValue::Overflow),
+        }
+    }
+
+    fn undo(&self, value: u128, b: u128) -> Undo {
+        if b == 0 {
+            return match value {
+                1 => Undo::Range(ANY),
+                _ => Undo::None,
+            };
+        }
+        // The largest `a` with `a ** b <= value`, by bisection.
+        let (mut lo, mut hi) = (0, value);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            match self.apply(mid, b) {
+                Ok(p) if p <= value => lo = mid,
+                _ => hi = mid - 1,
+            }
+        }
+        (self.apply(lo, b) == Ok(value)).then_some(lo).into()
+    }
+}
+
+/// The operators an equation can use, tried in the order they were added.
+pub struct Registry {
+    ops: Vec<Box<dyn Operator>>,
+}
+
+impl Default for Registry {
+    /// The operators of part 1: `+` and `*`.
+    fn default() -> Registry {
+        Registry::empty().with(Add).with(Mul)
+    }
+}
+
+impl Registry {
+    pub fn empty() -> Registry {
+        Registry { ops: Vec::new() }
+    }
+
+    pub fn with(mut self, op: impl Operator + 'static) -> Registry {
+        assert!(
+            self.ops.iter().all(|o| o.symbol() != op.symbol()),
+            "`{}` is already an operator",
+            op.symbol()
+        );
+        self.ops.push(Box::new(op));
+        self
+    }
+
+    pub fn ops(&self) -> &[Box<dyn Operator>] {
+        &self.ops
+    }
+
+    /// Evaluates `operands` left to right with the operators at indices `ops`,
+    /// or `None` if some step has no value.
+    pub fn eval(&self, operands: &[u64], ops: &[usize]) -> Result<Option<u128>, Overflow> {
+        let mut value = operands[0] as u128;
+        for (i, (&op, &b)) in ops.iter().zip(&operands[1..]).enumerate() {
+            value = match self.ops[op].apply(value, b as u128) {
+                Ok(value) => value,
+                Err(NoValue::Undefined) => return Ok(None),
+                Err(NoValue::Overflow) => return Err(Overflow { op: i }),
+            };
+        }
+        Ok(Some(value))
+    }
+
+    /// The equation with the operators at indices `ops`, as in
+    /// `3267: 81 + 40 * 27`.
+    pub fn show(&self, value: u64, operands: &[u64], ops: &[usize]) -> String {
+        let mut s = format!("{}: {}", value, operands[0]);
+        for (&op, operand) in ops.iter().zip(&operands[1..]) {
+            s += &format!(" {} {}", self.ops[op].symbol(), operand);
+        }
+        s
+    }
+}
+
+/// An operator of an equation whose result is larger than `u128::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    /// Which operator of the equation, counting from 0.
+    pub op: usize,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "overflow at operator {}", self.op + 1)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_digits() {
+        assert_eq!(digits(0), 1);
+        assert_eq!(digits(9), 1);
+        assert_eq!(digits(10), 2);
+        assert_eq!(digits(999_999_999_999_999_999), 18);
+        assert_eq!(digits(1_000_000_000_000_000_000), 19);
+        assert_eq!(digits(u64::MAX as u128), 20);
+        assert_eq!(digits(u128::MAX), 39);
+        assert_eq!(Concat.apply(12, 0), Ok(120));
+        let big = 10_u128.pow(38);
+        assert_eq!(Concat.apply(2, big), Err(NoValue::Overflow));
+        assert_eq!(Concat.apply(0, big), Ok(big));
+    }
+
+    /// Each `undo` agrees with `apply` on small numbers.
+    #[test]
+    fn undo_inverts_apply() {
+        let ops: [&dyn Operator; 8] = [&Add, &Mul, &Concat, &Sub, &Xor, &Min, &Max, &Pow];
+        for op in ops {
+            for value in 0..40 {
+                for b in 0..8 {
+                    let lefts: Vec<u128> =
+                        (0..200).filter(|&a| op.apply(a, b) == Ok(value)).collect();
+                    let undone: Vec<u128> = match op.undo(value, b) {
+                        Undo::None | Undo::Overflow => Vec::new(),
+                        Undo::One(a) => vec![a],
+                        Undo::Range(range) => (0..200).filter(|a| range.contains(a)).collect(),
+                        Undo::Unknown => lefts.clone(),
+                    };
+                    assert_eq!(undone, lefts, "{} {} {}", value, op.symbol(), b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let registry = Registry::default().with(Concat).with(Sub);
+        assert_eq!(registry.eval(&[81, 40, 27], &[0, 1]), Ok(Some(3267)));
+        let max = u64::MAX;
+        assert_eq!(
+            registry.eval(&[max, max, 2], &[1, 0]),
+            Ok(Some(max as u128 * max as u128 + 2))
+        );
+        assert_eq!(
+            registry.eval(&[max, max, max, 2], &[1, 1, 0]),
+            Err(Overflow { op: 1 })
+        );
+        assert_eq!(registry.eval(&[1, 2], &[3]), Ok(None));
+        assert_eq!(Overflow { op: 0 }.to_string(), "overflow at operator 1");
+    }
+}
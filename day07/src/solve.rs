@@ -0,0 +1,177 @@
+//! Finds the operators of an equation by working back from its value: the
+//! last operator must undo into a value for the operands before it, which
+//! rules out most operators at each step.
+
+use crate::{NoValue, Overflow, Registry, Undo};
+use std::cell::Cell;
+
+/// The first overflow met while solving, kept when no solution is found
+/// after all.
+type Overflows = Cell<Option<Overflow>>;
+
+fn note(overflows: &Overflows, op: usize) {
+    if overflows.get().is_none() {
+        overflows.set(Some(Overflow { op }));
+    }
+}
+
+impl Registry {
+    /// The indices of the operators to put between `operands`, evaluated left
+    /// to right, for them to equal `value`. When no such operators are found
+    /// but some would have taken a value past `u128::MAX`, there is no telling
+    /// whether the equation holds, and the overflow is returned.
+    pub fn solve(&self, value: u64, operands: &[u64]) -> Result<Option<Vec<usize>>, Overflow> {
+        let operands: Vec<u128> = operands.iter().map(|&n| n as u128).collect();
+        let overflows = Cell::new(None);
+        match self.work_back(value as u128, &operands, &overflows) {
+            Some(solution) => Ok(Some(solution)),
+            None => overflows.get().map_or(Ok(None), Err),
+        }
+    }
+
+    fn work_back(
+        &self,
+        value: u128,
+        operands: &[u128],
+        overflows: &Overflows,
+    ) -> Option<Vec<usize>> {
+        let (&last, rest) = operands.split_last()?;
+        if rest.is_empty() {
+            return (value == last).then(Vec::new);
+        }
+        let at = rest.len() - 1;
+        for (i, op) in self.ops().iter().enumerate() {
+            let solution = match op.undo(value, last) {
+                Undo::None => None,
+                Undo::One(a) => self.work_back(a, rest, overflows),
+                // The operands before can't add up to more than a `u64`.
+                Undo::Overflow if at == 0 => None,
+                Undo::Overflow => {
+                    note(overflows, at - 1);
+                    None
+                }
+                // Several values of the left side work, so they can't be
+                // worked back from.
+                Undo::Range(range) => self.search(rest[0], &rest[1..], 0, overflows, &|a| {
+                    Ok(range.contains(&a))
+                }),
+                Undo::Unknown => self.search(rest[0], &rest[1..], 0, overflows, &|a| {
+                    op.apply(a, last).map(|v| v == value)
+                }),
+            };
+            if let Some(mut solution) = solution {
+                solution.push(i);
+                return Some(solution);
+            }
+        }
+        None
+    }
+
+    /// The operators for `operands` that give a value accepted by `accept`,
+    /// from the value `acc` so far, trying every combination. `at` is the
+    /// index of the next operator in the equation.
+    fn search(
+        &self,
+        acc: u128,
+        operands: &[u128],
+        at: usize,
+        overflows: &Overflows,
+        accept: &dyn Fn(u128) -> Result<bool, NoValue>,
+    ) -> Option<Vec<usize>> {
+        let Some((&b, rest)) = operands.split_first() else {
+            return match accept(acc) {
+                Ok(accepted) => accepted.then(Vec::new),
+                Err(NoValue::Undefined) => None,
+                Err(NoValue::Overflow) => {
+                    note(overflows, at);
+                    None
+                }
+            };
+        };
+        self.ops().iter().enumerate().find_map(|(i, op)| {
+            let acc = match op.apply(acc, b) {
+                Ok(acc) => acc,
+                Err(NoValue::Undefined) => return None,
+                Err(NoValue::Overflow) => {
+                    note(overflows, at);
+                    return None;
+                }
+            };
+            let mut solution = self.search(acc, rest, at + 1, overflows, accept)?;
+            solution.insert(0, i);
+            Some(solution)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn solutions() {
+        let registry = Registry::default().with(Concat);
+        assert_eq!(registry.solve(190, &[10, 19]), Ok(Some(vec![1])));
+        assert_eq!(
+            registry.solve(7290, &[6, 8, 6, 15]),
+            Ok(Some(vec![1, 2, 1]))
+        );
+        assert_eq!(Registry::default().solve(7290, &[6, 8, 6, 15]), Ok(None));
+        assert_eq!(
+            Registry::default().solve(0, &[3, 4, 0]),
+            Ok(Some(vec![0, 1]))
+        );
+        assert_eq!(registry.solve(100, &[10, 0]), Ok(Some(vec![2])));
+        assert_eq!(registry.solve(5, &[5]), Ok(Some(vec![])));
+        assert_eq!(
+            registry.show(3267, &[81, 40, 27], &[0, 1]),
+            "3267: 81 + 40 * 27"
+        );
+    }
+
+    #[test]
+    fn large_values() {
+        // Values past `u64::MAX` along the way are fine.
+        let max = u64::MAX;
+        let registry = Registry::default();
+        assert_eq!(registry.solve(0, &[max, max, 0]), Ok(Some(vec![0, 1])));
+        let sub = Registry::empty().with(Mul).with(Sub);
+        assert_eq!(sub.solve(2, &[1 << 63, 2, max - 1]), Ok(Some(vec![0, 1])));
+
+        // Past `u128::MAX`, there is no knowing.
+        let mul = Registry::empty().with(Mul);
+        assert_eq!(mul.solve(0, &[max, max, max, 0]), Err(Overflow { op: 1 }));
+        assert_eq!(
+            registry.solve(0, &[max, max, max, 0]),
+            Ok(Some(vec![0, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn other_operators() {
+        let registry = Registry::empty().with(Sub).with(Xor).with(Max).with(Pow);
+        let operands = [5, 2, 3, 10, 2];
+        assert_eq!(registry.eval(&operands, &[0, 3, 2, 1]), Ok(Some(25)));
+        let solution = registry.solve(25, &operands).unwrap().unwrap();
+        assert_eq!(registry.eval(&operands, &solution), Ok(Some(25)));
+        assert_eq!(
+            Registry::empty().with(Min).solve(3, &[4, 7, 3]),
+            Ok(Some(vec![0, 0]))
+        );
+
+        /// An operator that can only be solved forwards.
+        struct Mod;
+        impl Operator for Mod {
+            fn symbol(&self) -> &'static str {
+                "%"
+            }
+
+            fn apply(&self, a: u128, b: u128) -> Result<u128, NoValue> {
+                a.checked_rem(b).ok_or(NoValue::Undefined)
+            }
+        }
+        let registry = Registry::default().with(Mod);
+        let solution = registry.solve(2, &[7, 3, 5, 4]).unwrap().unwrap();
+        assert_eq!(registry.eval(&[7, 3, 5, 4], &solution), Ok(Some(2)));
+    }
+}
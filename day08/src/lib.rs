@@ -0,0 +1,116 @@
+use common::{geom::Vec2, ParseError, Solution};
+use grid::Grid;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+type Pos = Vec2<i32>;
+
+#[derive(Debug)]
+pub struct Scan {
+    h: i32,
+    w: i32,
+    pos_by_antenna: HashMap<char, Vec<Pos>>,
+}
+
+fn parse(contents: &str) -> Result<Scan, ParseError> {
+    let grid: Grid<char> = contents.parse()?;
+    let mut scan = Scan {
+        h: grid.height() as i32,
+        w: grid.width() as i32,
+        pos_by_antenna: HashMap::new(),
+    };
+    for (pos, &c) in grid.iter() {
+        if c != '.' {
+            scan.pos_by_antenna.entry(c).or_default().push(pos);
+        }
+    }
+    Ok(scan)
+}
+
+impl Scan {
+    fn contains(&self, p: Pos) -> bool {
+        p.y >= 0 && p.y < self.h && p.x >= 0 && p.x < self.w
+    }
+}
+
+fn get_antinode(scan: &Scan, p1: &Pos, p2: &Pos) -> Option<Pos> {
+    let p = *p2 + (*p2 - *p1);
+    if scan.contains(p) {
+        Some(p)
+    } else {
+        None
+    }
+}
+
+fn get_antinode2(scan: &Scan, p1: &Pos, p2: &Pos) -> Vec<Pos> {
+    let v = *p2 - *p1;
+    let mut res = Vec::new();
+    let mut p = *p1;
+    loop {
+        p += v;
+        if !scan.contains(p) {
+            break;
+        }
+        res.push(p);
+    }
+    res
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Scan;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(scan: &Self::Input) -> usize {
+        let mut unique_pos = HashSet::new();
+        for pos in scan.pos_by_antenna.values() {
+            for pts in pos.iter().permutations(2) {
+                match pts.as_slice() {
+                    [p1, p2] => {
+                        if let Some(p) = get_antinode(scan, p1, p2) {
+                            unique_pos.insert(p);
+                        }
+                    }
+                    _ => panic!("unexpected permutation"),
+                }
+            }
+        }
+        unique_pos.len()
+    }
+
+    fn part2(scan: &Self::Input) -> usize {
+        let mut unique_pos = HashSet::new();
+        for pos in scan.pos_by_antenna.values() {
+            for pts in pos.iter().permutations(2) {
+                match pts.as_slice() {
+                    [p1, p2] => {
+                        for p in get_antinode2(scan, p1, p2) {
+                            unique_pos.insert(p);
+                        }
+                    }
+                    _ => panic!("unexpected permutation"),
+                }
+            }
+        }
+        unique_pos.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uneven_rows() {
+        assert_eq!(
+            parse("a..\n..\n..a").unwrap_err().to_string(),
+            "line 2, column 1: row has 2 columns, expected 3: `..`"
+        );
+    }
+}
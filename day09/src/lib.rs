@@ -1,7 +1,8 @@
+use common::{ParseError, Solution};
 use std::collections::BinaryHeap;
 
-#[derive(PartialEq, Eq, Debug)]
-struct MemBlk {
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct MemBlk {
     pos: usize,
     len: usize,
 }
@@ -19,36 +20,39 @@ impl PartialOrd for MemBlk {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-struct File {
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+pub struct File {
     // Sort by pos in ascending order.
     pos: usize,
     id: u32,
     size: usize,
 }
 
-fn parse(input: &str) -> (BinaryHeap<File>, BinaryHeap<MemBlk>) {
+fn parse(input: &str) -> Result<(BinaryHeap<File>, BinaryHeap<MemBlk>), ParseError> {
     let mut files = BinaryHeap::new();
     let mut memblks = BinaryHeap::new();
     let mut pos: usize = 0;
     let mut file_id = 0;
-    let mut iter = input.chars();
+    let digit = |(i, c): (usize, char)| {
+        c.to_digit(10)
+            .map(|d| d as usize)
+            .ok_or_else(|| ParseError::at(input, &input[i..i + c.len_utf8()], "expected a digit"))
+    };
+    let mut iter = input.char_indices();
     while let Some(file_size) = iter.next() {
         let file = File {
             id: file_id,
-            pos: pos,
-            size: file_size.to_digit(10).unwrap() as usize,
+            pos,
+            size: digit(file_size)?,
         };
         file_id += 1;
         pos += file.size;
         files.push(file);
         match iter.next() {
             Some(freesize) => {
-                if freesize != '0' {
-                    let blk = MemBlk {
-                        pos,
-                        len: freesize.to_digit(10).unwrap() as usize,
-                    };
+                let len = digit(freesize)?;
+                if len != 0 {
+                    let blk = MemBlk { pos, len };
                     pos += blk.len;
                     memblks.push(blk);
                 }
@@ -56,7 +60,7 @@ fn parse(input: &str) -> (BinaryHeap<File>, BinaryHeap<MemBlk>) {
             None => break,
         }
     }
-    (files, memblks)
+    Ok((files, memblks))
 }
 
 fn checksum(files: &[File]) -> u64 {
@@ -67,18 +71,79 @@ fn checksum(files: &[File]) -> u64 {
     })
 }
 
-fn part1(contents: &str) -> u64 {
-    let (mut files, mut memblks) = parse(&contents);
-    let mut final_files = BinaryHeap::new();
-    // Get last file.
-    while let Some(mut file) = files.pop() {
-        // Get first mem block.
-        match memblks.pop() {
-            Some(memblk) => {
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = (BinaryHeap<File>, BinaryHeap<MemBlk>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((files, memblks): &Self::Input) -> u64 {
+        let (mut files, mut memblks) = (files.clone(), memblks.clone());
+        let mut final_files = BinaryHeap::new();
+        // Get last file.
+        while let Some(mut file) = files.pop() {
+            // Get first mem block.
+            match memblks.pop() {
+                Some(memblk) => {
+                    if memblk.pos >= file.pos {
+                        final_files.push(file);
+                        memblks.push(memblk);
+                        continue;
+                    }
+                    // If file fits in memory block.
+                    if file.size <= memblk.len {
+                        let new_blk = MemBlk {
+                            pos: memblk.pos + file.size,
+                            len: memblk.len - file.size,
+                        };
+                        file.pos = memblk.pos;
+                        final_files.push(file);
+                        if new_blk.len > 0 {
+                            memblks.push(new_blk);
+                        }
+                    } else if file.size > memblk.len {
+                        // If file does not fit in memory block.
+                        let remaining_file = File {
+                            id: file.id,
+                            // pos: file.pos + memblk.len,
+                            // Trick: we shift the file to the right by the size of the moved chunk.
+                            pos: file.pos,
+                            size: file.size - memblk.len,
+                        };
+                        file.size = memblk.len;
+                        file.pos = memblk.pos;
+                        final_files.push(file);
+                        files.push(remaining_file);
+                    }
+                }
+                None => {
+                    break;
+                }
+            }
+        }
+        checksum(final_files.as_slice())
+    }
+
+    fn part2((files, memblks): &Self::Input) -> u64 {
+        let (mut files, mut memblks) = (files.clone(), memblks.clone());
+        let mut final_files = BinaryHeap::new();
+        // Get last file.
+        'outer: while let Some(mut file) = files.pop() {
+            let mut new_blks = BinaryHeap::new();
+            // Get first free memory block.
+            while let Some(memblk) = memblks.pop() {
                 if memblk.pos >= file.pos {
                     final_files.push(file);
-                    memblks.push(memblk);
-                    continue;
+                    new_blks.push(memblk);
+                    for blk in new_blks {
+                        memblks.push(blk);
+                    }
+                    continue 'outer;
                 }
                 // If file fits in memory block.
                 if file.size <= memblk.len {
@@ -89,81 +154,25 @@ fn part1(contents: &str) -> u64 {
                     file.pos = memblk.pos;
                     final_files.push(file);
                     if new_blk.len > 0 {
-                        memblks.push(new_blk);
+                        new_blks.push(new_blk);
                     }
+                    for blk in new_blks {
+                        memblks.push(blk);
+                    }
+                    continue 'outer;
                 } else if file.size > memblk.len {
-                    // If file does not fit in memory block.
-                    let remaining_file = File {
-                        id: file.id,
-                        // pos: file.pos + memblk.len,
-                        // Trick: we shift the file to the right by the size of the moved chunk.
-                        pos: file.pos,
-                        size: file.size - memblk.len,
-                    };
-                    file.size = memblk.len;
-                    file.pos = memblk.pos;
-                    final_files.push(file);
-                    files.push(remaining_file);
+                    new_blks.push(memblk);
                 }
             }
-            None => {
-                break;
+            // If we reach here, we have not found a memory block that fits the file.
+            final_files.push(file);
+            for blk in new_blks {
+                memblks.push(blk);
             }
         }
-    }
-    checksum(final_files.as_slice())
-}
 
-fn part2(contents: &str) -> u64 {
-    let (mut files, mut memblks) = parse(&contents);
-    let mut final_files = BinaryHeap::new();
-    // Get last file.
-    'outer: while let Some(mut file) = files.pop() {
-        let mut new_blks = BinaryHeap::new();
-        // Get first free memory block.
-        while let Some(memblk) = memblks.pop() {
-            if memblk.pos >= file.pos {
-                final_files.push(file);
-                new_blks.push(memblk);
-                for blk in new_blks {
-                    memblks.push(blk);
-                }
-                continue 'outer;
-            }
-            // If file fits in memory block.
-            if file.size <= memblk.len {
-                let new_blk = MemBlk {
-                    pos: memblk.pos + file.size,
-                    len: memblk.len - file.size,
-                };
-                file.pos = memblk.pos;
-                final_files.push(file);
-                if new_blk.len > 0 {
-                    new_blks.push(new_blk);
-                }
-                for blk in new_blks {
-                    memblks.push(blk);
-                }
-                continue 'outer;
-            } else if file.size > memblk.len {
-                new_blks.push(memblk);
-            }
-        }
-        // If we reach here, we have not found a memory block that fits the file.
-        final_files.push(file);
-        for blk in new_blks {
-            memblks.push(blk);
-        }
+        checksum(final_files.as_slice())
     }
-
-    return checksum(&final_files.as_slice());
-}
-
-fn main() {
-    let contents = std::fs::read_to_string(std::env::args().nth(1).expect("Missing filename"))
-        .expect("Failed to read file");
-    println!("{}", part1(&contents));
-    println!("{}", part2(&contents));
 }
 
 #[cfg(test)]
@@ -232,7 +241,7 @@ mod tests {
     #[test]
     fn parse_input() {
         let input = "123450";
-        let (files, memblks) = parse(input);
+        let (files, memblks) = parse(input).unwrap();
         assert_eq!(
             files.into_sorted_vec(),
             vec![
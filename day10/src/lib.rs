@@ -0,0 +1,93 @@
+use common::{ParseError, Solution};
+use grid::{Grid, Pos};
+use std::collections::{HashSet, VecDeque};
+
+type Path = Vec<Pos>;
+type Map = Grid<i32>;
+
+fn neighbors(pos: &Pos, map: &Map) -> Vec<Pos> {
+    let curr_height = map[*pos];
+    map.neighbors4(*pos)
+        .filter(|(_, &height)| height - curr_height == 1)
+        .map(|(new_pos, _)| new_pos)
+        .collect()
+}
+
+fn count_paths(from: &Pos, map: &Map) -> i32 {
+    let mut queue: VecDeque<Pos> = VecDeque::new();
+    let mut visited: HashSet<Pos> = HashSet::new();
+    queue.push_back(*from);
+    let mut sum = 0;
+    while let Some(from) = queue.pop_front() {
+        if visited.contains(&from) {
+            continue;
+        }
+        visited.insert(from);
+        if let Some(height) = map.get(from) {
+            if *height == 9 {
+                sum += 1
+            }
+        }
+        neighbors(&from, map).iter().for_each(|n| {
+            queue.push_back(*n);
+        });
+    }
+    sum
+}
+
+fn count_paths2(from: &Pos, map: &Map) -> i32 {
+    let mut queue: VecDeque<(Path, Pos)> = VecDeque::new();
+    let mut visited: HashSet<(Path, Pos)> = HashSet::new();
+    queue.push_back((vec![(*from)], *from));
+    let mut sum = 0;
+    while let Some(entry) = queue.pop_front() {
+        if visited.contains(&entry) {
+            continue;
+        }
+        visited.insert(entry.clone());
+        let (path, from) = entry;
+        if let Some(height) = map.get(from) {
+            if *height == 9 {
+                sum += 1
+            }
+        }
+        neighbors(&from, map).iter().for_each(|n| {
+            let mut new_path = path.clone();
+            new_path.push(from);
+            queue.push_back((new_path, *n));
+        });
+    }
+    sum
+}
+
+fn parse(input: &str) -> Result<Map, ParseError> {
+    let grid: Grid<char> = input.parse()?;
+    Ok(grid.map(|c| c.to_digit(10).map_or(1000, |d| d as i32)))
+}
+
+fn zeros(map: &Map) -> Vec<Pos> {
+    map.iter()
+        .filter(|(_k, v)| **v == 0)
+        .map(|(k, _v)| k)
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Map;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(map: &Self::Input) -> i32 {
+        zeros(map).iter().map(|p| count_paths(p, map)).sum()
+    }
+
+    fn part2(map: &Self::Input) -> i32 {
+        zeros(map).iter().map(|p| count_paths2(p, map)).sum()
+    }
+}
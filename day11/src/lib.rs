@@ -1,3 +1,4 @@
+use common::{parse_num, ParseError, Solution};
 use std::collections::HashMap;
 
 fn count_len(stone: i64, times: i64, cache: &mut HashMap<(i64, i64), usize>) -> usize {
@@ -22,7 +23,7 @@ fn count_len(stone: i64, times: i64, cache: &mut HashMap<(i64, i64), usize>) ->
     res
 }
 
-fn run(stones: &Vec<i64>, times: i64) -> usize {
+fn run(stones: &[i64], times: i64) -> usize {
     let mut cache = HashMap::new();
     stones
         .iter()
@@ -30,13 +31,29 @@ fn run(stones: &Vec<i64>, times: i64) -> usize {
         .sum()
 }
 
-fn main() {
-    let stones = std::fs::read_to_string(std::env::args().nth(1).unwrap())
-        .unwrap()
-        .split(" ")
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<i64>>();
+fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|x| parse_num(input, x))
+        .collect()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<i64>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(stones: &Self::Input) -> usize {
+        run(stones, 25)
+    }
 
-    println!("{}", run(&stones, 25));
-    println!("{}", run(&stones, 75));
+    fn part2(stones: &Self::Input) -> usize {
+        run(stones, 75)
+    }
 }
@@ -1,7 +1,9 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
-type Pos = (i32, i32); // y, x
-type Grid = HashMap<Pos, char>;
+use common::{
+    geom::{Dir4, Dir8},
+    ParseError, Solution,
+};
+use grid::{Grid, Pos};
+use std::collections::{HashSet, VecDeque};
 
 #[derive(Debug)]
 struct Entry {
@@ -10,11 +12,11 @@ struct Entry {
     cells: HashSet<Pos>,
 }
 
-fn flood_fill(pos: &Pos, grid: &Grid, visited: &mut HashSet<Pos>) -> Option<Entry> {
+fn flood_fill(pos: &Pos, grid: &Grid<char>, visited: &mut HashSet<Pos>) -> Option<Entry> {
     if visited.contains(pos) {
         return None;
     }
-    let c = grid.get(&pos).unwrap();
+    let c = &grid[*pos];
     let mut queue = VecDeque::from([*pos]);
     let mut entry = Entry {
         area: 0,
@@ -29,10 +31,9 @@ fn flood_fill(pos: &Pos, grid: &Grid, visited: &mut HashSet<Pos>) -> Option<Entr
         visited.insert(pos);
         entry.area += 1;
         entry.cells.insert(pos);
-        let (y, x) = pos;
-        for (dy, dx) in [(-1, 0), (1, 0), (0, -1), (0, 1)].iter() {
-            let np = (y + dy, x + dx);
-            if let Some(nc) = grid.get(&np) {
+        for dir in Dir4::ALL {
+            let np = pos + dir.delta();
+            if let Some(nc) = grid.get(np) {
                 if nc != c {
                     entry.peri += 1;
                 } else if !visited.contains(&np) {
@@ -46,34 +47,34 @@ fn flood_fill(pos: &Pos, grid: &Grid, visited: &mut HashSet<Pos>) -> Option<Entr
     Some(entry)
 }
 
-fn find_areas(grid: &Grid) -> Vec<Entry> {
+fn find_areas(grid: &Grid<char>) -> Vec<Entry> {
     let mut visited = HashSet::new();
     let mut entries = Vec::new();
-    for pos in grid.keys() {
-        if let Some(entry) = flood_fill(pos, grid, &mut visited) {
+    for pos in grid.positions() {
+        if let Some(entry) = flood_fill(&pos, grid, &mut visited) {
             entries.push(entry);
         }
     }
     entries
 }
 
-fn compute_fence_cost1(grid: &Grid) -> i32 {
-    find_areas(&grid).iter().map(|e| e.area * e.peri).sum()
+fn compute_fence_cost1(grid: &Grid<char>) -> i32 {
+    find_areas(grid).iter().map(|e| e.area * e.peri).sum()
 }
 
-fn compute_fence_cost2(grid: &Grid) -> i32 {
-    let areas = find_areas(&grid);
+fn compute_fence_cost2(grid: &Grid<char>) -> i32 {
+    let areas = find_areas(grid);
     let mut sum = 0;
     for a in areas.iter() {
         let mut sides = 0;
         for pos in &a.cells {
-            let (y, x) = *pos;
-            let c = grid.get(&pos).unwrap();
+            let c = &grid[*pos];
             let default = &'☠';
-            let top = grid.get(&(y - 1, x)).unwrap_or(default);
-            let right = grid.get(&(y, x + 1)).unwrap_or(default);
-            let bottom = grid.get(&(y + 1, x)).unwrap_or(default);
-            let left = grid.get(&(y, x - 1)).unwrap_or(default);
+            let at = |dir: Dir8| grid.get(*pos + dir.delta()).unwrap_or(default);
+            let top = at(Dir8::Up);
+            let right = at(Dir8::Right);
+            let bottom = at(Dir8::Down);
+            let left = at(Dir8::Left);
             if top != c && right != c {
                 sides += 1;
             }
@@ -87,19 +88,19 @@ fn compute_fence_cost2(grid: &Grid) -> i32 {
                 sides += 1;
             }
             // Top right corner.
-            if top == c && right == c && grid.get(&(y - 1, x + 1)).unwrap_or(default) != c {
+            if top == c && right == c && at(Dir8::UpRight) != c {
                 sides += 1;
             }
             // Bottom right corner.
-            if bottom == c && right == c && grid.get(&(y + 1, x + 1)).unwrap_or(default) != c {
+            if bottom == c && right == c && at(Dir8::DownRight) != c {
                 sides += 1;
             }
             // Bottom left corner.
-            if bottom == c && left == c && grid.get(&(y + 1, x - 1)).unwrap_or(default) != c {
+            if bottom == c && left == c && at(Dir8::DownLeft) != c {
                 sides += 1;
             }
             // Top left corner.
-            if top == c && left == c && grid.get(&(y - 1, x - 1)).unwrap_or(default) != c {
+            if top == c && left == c && at(Dir8::UpLeft) != c {
                 sides += 1;
             }
         }
@@ -115,18 +116,22 @@ fn compute_fence_cost2(grid: &Grid) -> i32 {
     sum
 }
 
-fn main() {
-    let grid: Grid = std::fs::read_to_string(std::env::args().nth(1).unwrap())
-        .unwrap()
-        .lines()
-        .enumerate()
-        .flat_map(|(y, l)| {
-            l.chars()
-                .enumerate()
-                .map(move |(x, c)| ((y as i32, x as i32), c))
-        })
-        .collect();
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Grid<char>;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
 
-    println!("{}", compute_fence_cost1(&grid));
-    println!("{}", compute_fence_cost2(&grid));
+    fn part1(grid: &Self::Input) -> i32 {
+        compute_fence_cost1(grid)
+    }
+
+    fn part2(grid: &Self::Input) -> i32 {
+        compute_fence_cost2(grid)
+    }
 }
@@ -0,0 +1,102 @@
+use common::{geom::Vec2, parse_num, ParseError, Solution};
+use std::collections::{HashSet, VecDeque};
+
+use regex::Regex;
+
+type Pos = Vec2<i64>;
+
+#[derive(Debug)]
+pub struct Blk {
+    da: Pos,
+    db: Pos,
+    prize: Pos,
+}
+
+fn parse_block(input: &str, block: &str) -> Result<Blk, ParseError> {
+    let re = Regex::new(
+        r"Button A: X\+(\d+), Y\+(\d+)\nButton B: X\+(\d+), Y\+(\d+)\nPrize: X\=(\d+), Y=(\d+)",
+    )
+    .unwrap();
+    let caps = re.captures(block).ok_or_else(|| {
+        ParseError::at(input, block, "expected button A, button B and prize lines")
+    })?;
+    let num = |i| parse_num::<i64>(input, caps.get(i).unwrap().as_str());
+    Ok(Blk {
+        da: Vec2::new(num(1)?, num(2)?),
+        db: Vec2::new(num(3)?, num(4)?),
+        prize: Vec2::new(num(5)?, num(6)?),
+    })
+}
+
+fn min_cost(blk: &Blk) -> Option<i32> {
+    let mut queue = VecDeque::new();
+    let mut seen = HashSet::new();
+    queue.push_back((Pos::default(), 0));
+    let mut min = Option::None;
+    while let Some((pos, cost)) = queue.pop_front() {
+        if seen.contains(&(pos, cost)) || (min.is_some() && cost >= min.unwrap()) {
+            continue;
+        }
+        seen.insert((pos, cost));
+        if pos.y > blk.prize.y || pos.x > blk.prize.x {
+            continue;
+        }
+        if pos == blk.prize && (min.is_none() || cost < min.unwrap()) {
+            min = Some(cost);
+        }
+        queue.push_back((pos + blk.da, cost + 3));
+        queue.push_back((pos + blk.db, cost + 1));
+    }
+    min
+}
+
+const ADD: i64 = 10000000000000;
+
+fn solve(blk: &Blk) -> Option<i64> {
+    let (ax, ay) = (blk.da.x, blk.da.y);
+    let (bx, by) = (blk.db.x, blk.db.y);
+    let (px, py) = (ADD + blk.prize.x, ADD + blk.prize.y);
+
+    let a_nom = bx * py - (by * px);
+    let a_den = bx * ay - (ax * by);
+    if a_nom % a_den != 0 {
+        return None;
+    }
+    let a = a_nom / a_den;
+
+    let b_den = bx;
+    let b_nom = px - ax * a;
+    if b_nom % b_den != 0 {
+        return None;
+    }
+    let b = b_nom / b_den;
+
+    Some(a * 3 + b)
+}
+
+fn parse(input: &str) -> Result<Vec<Blk>, ParseError> {
+    input
+        .split("\n\n")
+        .map(|block| parse_block(input, block))
+        .collect()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Blk>;
+    type Output1 = i32;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(blocks: &Self::Input) -> i32 {
+        blocks.iter().map(min_cost).map(|v| v.unwrap_or(0)).sum()
+    }
+
+    fn part2(blocks: &Self::Input) -> i64 {
+        blocks.iter().map(solve).map(|v| v.unwrap_or(0)).sum()
+    }
+}
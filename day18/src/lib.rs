@@ -0,0 +1,183 @@
+use common::{geom::Vec2, parse_num, Params, ParseError, Solution};
+use grid::Pos;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+type Cost = usize;
+
+type Grid = grid::Grid<char>;
+
+#[derive(Debug)]
+pub struct Memory {
+    exit: Pos,
+    n_bytes: usize,
+    bytes: Vec<Pos>,
+}
+
+// Largest coordinate of the memory space and number of fallen bytes of the real
+// puzzle input, unless the input has `# size=<n>` and `# bytes=<n>` lines at
+// the top.
+const SIZE: i32 = 70;
+const N_BYTES: usize = 1024;
+
+// Largest `# size=<n>`, which keeps the grid to a million cells.
+const MAX_SIZE: i32 = 1000;
+
+fn parse_input(input: &str) -> Result<Memory, ParseError> {
+    let (params, bytes) = Params::parse(input, &["size", "bytes"])?;
+    let size = params.get_valid(
+        "size",
+        SIZE,
+        |n| (0..=MAX_SIZE).contains(n),
+        "size must be between 0 and 1000",
+    )?;
+    let exit = Vec2::new(size, size);
+    let bytes = bytes
+        .lines()
+        .map(|l| {
+            let Some((x, y)) = l.split_once(",") else {
+                return Err(ParseError::at(input, l, "expected `<x>,<y>`"));
+            };
+            let pos = Vec2::new(parse_num(input, x)?, parse_num(input, y)?);
+            if pos.x < 0 || pos.y < 0 || pos.x > exit.x || pos.y > exit.y {
+                return Err(ParseError::at(input, l, "outside the memory space"));
+            }
+            Ok(pos)
+        })
+        .collect::<Result<Vec<Pos>, _>>()?;
+    let n_bytes = params.get_valid(
+        "bytes",
+        N_BYTES,
+        |&n| n <= bytes.len(),
+        "more bytes than the input has",
+    )?;
+    Ok(Memory {
+        exit,
+        n_bytes,
+        bytes,
+    })
+}
+
+fn heuristic_cost_estimate(start: &Pos, end: &Pos) -> Cost {
+    start.manhattan(*end) as Cost
+}
+
+fn a_star(start: &Pos, end: &Pos, grid: &Grid) -> Option<Cost> {
+    let mut open_set = BinaryHeap::from([Reverse((heuristic_cost_estimate(start, end), *start))]);
+
+    let mut g_score = HashMap::new();
+    g_score.insert(*start, 0);
+
+    let mut f_score = HashMap::new();
+    f_score.insert(*start, heuristic_cost_estimate(start, end));
+
+    while !open_set.is_empty() {
+        let (_, current) = open_set.pop().unwrap().0;
+
+        if current == *end {
+            return Some(g_score[&current]);
+        }
+
+        for (npos, _) in grid.neighbors4(current).filter(|(_, &c)| c != '#') {
+            let tentative_g_score = g_score[&current] + 1;
+            if tentative_g_score < *g_score.get(&npos).unwrap_or(&usize::MAX) {
+                g_score.insert(npos, tentative_g_score);
+                f_score.insert(
+                    npos,
+                    tentative_g_score + heuristic_cost_estimate(&npos, end),
+                );
+                open_set.push(Reverse((f_score[&npos], npos)));
+            }
+        }
+    }
+    None
+}
+
+fn initial_grid(memory: &Memory) -> Grid {
+    let exit = memory.exit;
+    let mut grid = Grid::new(exit.y as usize + 1, exit.x as usize + 1, '.');
+    for pos in memory.bytes.iter().take(memory.n_bytes) {
+        grid[*pos] = '#';
+    }
+    grid
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Memory;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(memory: &Self::Input) -> String {
+        let exit = memory.exit;
+        let grid = initial_grid(memory);
+
+        let min_cost = a_star(&Pos::default(), &exit, &grid);
+        min_cost.map_or("no path".to_string(), |cost| cost.to_string())
+    }
+
+    fn part2(memory: &Self::Input) -> String {
+        let exit = memory.exit;
+        let grid = initial_grid(memory);
+        // Start over from an empty memory space if it's already blocked.
+        let (mut grid, skip) = match a_star(&Pos::default(), &exit, &grid) {
+            Some(_) => (grid, memory.n_bytes),
+            None => (Grid::new(grid.height(), grid.width(), '.'), 0),
+        };
+
+        for byte in memory.bytes.iter().skip(skip) {
+            grid[*byte] = '#';
+            let cost = a_star(&Pos::default(), &exit, &grid);
+            if cost.is_none() {
+                return format!("{},{}", byte.x, byte.y);
+            }
+        }
+        "never blocked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocked_from_the_start() {
+        let memory = parse_input("# size=2\n# bytes=3\n0,1\n1,1\n1,0\n2,2").unwrap();
+        assert_eq!(Day18::part1(&memory), "no path");
+        assert_eq!(Day18::part2(&memory), "1,0");
+        let memory = parse_input("# size=2\n# bytes=1\n0,1\n1,1").unwrap();
+        assert_eq!(Day18::part1(&memory), "4");
+        assert_eq!(Day18::part2(&memory), "never blocked");
+    }
+
+    #[test]
+    fn params() {
+        let memory = parse_input("# size=2\n# bytes=1\n1,1\n2,0").unwrap();
+        assert_eq!((memory.exit, memory.n_bytes), (Vec2::new(2, 2), 1));
+        assert_eq!(
+            parse_input("# size=-1\n0,0").unwrap_err().to_string(),
+            "line 1, column 8: size must be between 0 and 1000: `-1`"
+        );
+        assert_eq!(
+            parse_input("# size=2\n# bytes=3\n1,1\n2,0")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 9: more bytes than the input has: `3`"
+        );
+        assert_eq!(
+            parse_input("# size=100000\n0,0").unwrap_err().to_string(),
+            "line 1, column 8: size must be between 0 and 1000: `100000`"
+        );
+        assert_eq!(
+            parse_input("# size=2\n3,0").unwrap_err().to_string(),
+            "line 2, column 1: outside the memory space: `3,0`"
+        );
+    }
+}
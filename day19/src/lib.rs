@@ -0,0 +1,68 @@
+use common::{ParseError, Solution};
+use std::collections::HashMap;
+
+fn count_combinations<'a>(
+    pattern: &'a str,
+    prefix: &'a str,
+    towels: &'a [String],
+    cache: &mut HashMap<(&'a str, &'a str), usize>,
+) -> usize {
+    let key = (prefix, pattern);
+    if let Some(&v) = cache.get(&key) {
+        return v;
+    }
+
+    if !pattern.starts_with(prefix) {
+        return 0;
+    }
+    let remainder = &pattern[prefix.len()..];
+    if remainder.is_empty() {
+        return 1;
+    }
+    let res = towels
+        .iter()
+        .map(|towel| count_combinations(remainder, towel, towels, cache))
+        .sum();
+    cache.insert(key, res);
+    res
+}
+
+fn parse(input: &str) -> Result<(Vec<String>, Vec<String>), ParseError> {
+    match input.split_once("\n\n") {
+        Some((towels, patterns)) => Ok((
+            towels.split(", ").map(String::from).collect(),
+            patterns.lines().map(String::from).collect(),
+        )),
+        None => Err(ParseError::new(
+            "expected towels and patterns separated by a blank line",
+        )),
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (Vec<String>, Vec<String>);
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1((towels, patterns): &Self::Input) -> usize {
+        let mut cache: HashMap<(&str, &str), usize> = HashMap::new();
+        patterns
+            .iter()
+            .filter(|pattern| count_combinations(pattern, "", towels, &mut cache) > 0)
+            .count()
+    }
+
+    fn part2((towels, patterns): &Self::Input) -> usize {
+        let mut cache: HashMap<(&str, &str), usize> = HashMap::new();
+        patterns
+            .iter()
+            .map(|pattern| count_combinations(pattern, "", towels, &mut cache))
+            .sum()
+    }
+}
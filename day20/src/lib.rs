@@ -0,0 +1,137 @@
+use common::{Params, ParseError, Solution};
+use grid::Pos;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+type Grid = grid::Grid<char>;
+
+fn find_path(
+    pos: &Pos,
+    end: &Pos,
+    path: Vec<Pos>,
+    grid: &Grid,
+    visited: &mut HashSet<Pos>,
+) -> Vec<Pos> {
+    if visited.contains(pos) {
+        return vec![];
+    }
+
+    visited.insert(*pos);
+
+    if pos == end {
+        let mut new_path = path.clone();
+        new_path.push(*pos);
+        return new_path;
+    }
+
+    let mut res = vec![];
+    for (neighbor, c) in grid.neighbors4(*pos) {
+        let mut new_visited = visited.clone();
+        let mut new_path = path.clone();
+        new_path.push(*pos);
+        match c {
+            '#' | 'S' => (),
+            '.' | 'E' => {
+                res.extend(find_path(&neighbor, end, new_path, grid, &mut new_visited));
+            }
+            c => panic!("unexpected char '{}'", c),
+        }
+    }
+    res
+}
+
+#[derive(Debug)]
+pub struct Racetrack {
+    grid: Grid,
+    /// The fewest picoseconds a cheat must save to be counted.
+    threshold: i32,
+}
+
+// Threshold of the real puzzle, unless the input has a `# threshold=<n>` line
+// at the top.
+const THRESHOLD: i32 = 100;
+
+fn count_gains(track: &Racetrack, max_dist: i32) -> usize {
+    let grid = &track.grid;
+    let start = grid.find('S').unwrap();
+    let end = grid.find('E').unwrap();
+
+    let path = find_path(&start, &end, vec![], grid, &mut HashSet::new());
+
+    let dist_by_node: HashMap<Pos, i32> = path
+        .iter()
+        .enumerate()
+        .map(|(i, &pos)| (pos, i as i32))
+        .collect();
+
+    let mut gains = 0;
+    for v in path.iter().combinations(2) {
+        let (a, b) = (v[0], v[1]);
+        if a.manhattan(*b) <= max_dist {
+            let gain = (dist_by_node[a] - dist_by_node[b]).abs() - a.manhattan(*b);
+            if gain >= track.threshold {
+                gains += 1;
+            }
+        }
+    }
+    gains
+}
+
+fn parse(input: &str) -> Result<Racetrack, ParseError> {
+    let (params, map) = Params::parse(input, &["threshold"])?;
+    let threshold = params.get_valid(
+        "threshold",
+        THRESHOLD,
+        |&n| n > 0,
+        "threshold must be positive",
+    )?;
+    let grid = Grid::parse_requiring(map, "#.SE", &['S', 'E']).map_err(|e| e.within(input, map))?;
+    Ok(Racetrack { grid, threshold })
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Racetrack;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(track: &Self::Input) -> usize {
+        count_gains(track, 2)
+    }
+
+    fn part2(track: &Self::Input) -> usize {
+        count_gains(track, 20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thresholds() {
+        let input = std::fs::read_to_string("../fixtures/day20/example.txt").unwrap();
+        let map = input.split_once('\n').unwrap().1;
+        // The cheats the puzzle lists as saving at least 20 and 76 picoseconds.
+        let track = parse(&format!("# threshold=20\n{}", map)).unwrap();
+        assert_eq!(Day20::part1(&track), 5);
+        let track = parse(&format!("# threshold=76\n{}", map)).unwrap();
+        assert_eq!(Day20::part2(&track), 3);
+
+        assert_eq!(
+            parse(&format!("# threshold=0\n{}", map))
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 13: threshold must be positive: `0`"
+        );
+        assert_eq!(
+            parse("# threshold=2\nS.x").unwrap_err().to_string(),
+            "line 2, column 3: unexpected character: `x`"
+        );
+    }
+}
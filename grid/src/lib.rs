@@ -0,0 +1,297 @@
+use common::{
+    geom::{Dir4, Dir8, Vec2},
+    ParseError,
+};
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// Signed so that stepping off an edge yields a position that is simply not
+/// in the grid.
+pub type Pos = Vec2<i32>;
+
+/// Dense 2D grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(Pos) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Vec2::new(x as i32, y as i32)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            height,
+            width,
+            cells,
+        }
+    }
+
+    pub fn new(height: usize, width: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![fill; height * width],
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if pos.y < 0 || pos.x < 0 || pos.y as usize >= self.height || pos.x as usize >= self.width {
+            return None;
+        }
+        Some(pos.y as usize * self.width + pos.x as usize)
+    }
+
+    fn pos_of(&self, index: usize) -> Pos {
+        Vec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.cells.len()).map(|i| self.pos_of(i))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (self.pos_of(i), c))
+    }
+
+    /// In-bounds orthogonal neighbours, clockwise starting from the top.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        Dir4::ALL.into_iter().filter_map(move |dir| {
+            let pos = pos + dir.delta();
+            self.get(pos).map(|c| (pos, c))
+        })
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours, clockwise starting from the top.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        Dir8::ALL.into_iter().filter_map(move |dir| {
+            let pos = pos + dir.delta();
+            self.get(pos).map(|c| (pos, c))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell equal to `value`, in row-major order.
+    pub fn find(&self, value: T) -> Option<Pos> {
+        self.cells
+            .iter()
+            .position(|c| *c == value)
+            .map(|i| self.pos_of(i))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", pos))
+    }
+}
+
+impl Grid<char> {
+    /// Like `parse`, but also rejects any character not in `allowed`.
+    pub fn parse_allowing(input: &str, allowed: &str) -> Result<Grid<char>, ParseError> {
+        for line in input.lines() {
+            if let Some((i, c)) = line.char_indices().find(|(_, c)| !allowed.contains(*c)) {
+                return Err(ParseError::at(
+                    input,
+                    &line[i..i + c.len_utf8()],
+                    "unexpected character",
+                ));
+            }
+        }
+        input.parse()
+    }
+
+    /// Like `parse_allowing`, but also rejects maps missing any of `tiles`,
+    /// such as the start and end of a maze.
+    pub fn parse_requiring(
+        input: &str,
+        allowed: &str,
+        tiles: &[char],
+    ) -> Result<Grid<char>, ParseError> {
+        let grid = Grid::parse_allowing(input, allowed)?;
+        for &tile in tiles {
+            if grid.find(tile).is_none() {
+                return Err(ParseError::new(format!("no `{}` tile in the map", tile)));
+            }
+        }
+        Ok(grid)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Grid<char>, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in input.lines() {
+            let len = line.chars().count();
+            match width {
+                Some(width) if width != len => {
+                    return Err(ParseError::at(
+                        input,
+                        line,
+                        format!("row has {} columns, expected {}", len, width),
+                    ));
+                }
+                _ => width = Some(len),
+            }
+            cells.extend(line.chars());
+            height += 1;
+        }
+        Ok(Grid {
+            height,
+            width: width.unwrap_or(0),
+            cells,
+        })
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for c in row {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = grid();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid.get(Vec2::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Vec2::new(0, 2)), None);
+        assert_eq!(grid.get(Vec2::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>().unwrap_err().to_string(),
+            "line 2, column 1: row has 2 columns, expected 3: `de`"
+        );
+        assert_eq!(
+            Grid::parse_allowing("ab\nca", "ab")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: unexpected character: `c`"
+        );
+        assert!(Grid::parse_requiring("S.E", "#.SE", &['S', 'E']).is_ok());
+        assert_eq!(
+            Grid::parse_requiring("S..", "#.SE", &['S', 'E'])
+                .unwrap_err()
+                .to_string(),
+            "no `E` tile in the map"
+        );
+    }
+
+    #[test]
+    fn find_and_mutate() {
+        let mut grid = grid();
+        assert_eq!(grid.find('e'), Some(Vec2::new(1, 1)));
+        assert_eq!(grid.find('z'), None);
+        grid[Vec2::new(1, 1)] = 'z';
+        *grid.get_mut(Vec2::new(0, 0)).unwrap() = 'y';
+        assert_eq!(grid.to_string(), "ybc\ndzf");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid();
+        let n4: Vec<char> = grid.neighbors4(Vec2::new(1, 0)).map(|(_, &c)| c).collect();
+        assert_eq!(n4, vec!['c', 'e', 'a']);
+        let n8: Vec<Pos> = grid.neighbors8(Vec2::new(0, 1)).map(|(p, _)| p).collect();
+        assert_eq!(n8, vec![Vec2::new(0, 0), Vec2::new(1, 0), Vec2::new(1, 1)]);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        let cols: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(cols, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+    }
+}